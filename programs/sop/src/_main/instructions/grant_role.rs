@@ -0,0 +1,45 @@
+use crate::{
+    _main::{main_state::MainState, role_state::{Role, RoleState}},
+    constants::{SEED_MAIN_STATE, SEED_ROLE_STATE},
+    error::MyError,
+};
+use anchor_lang::prelude::*;
+
+pub fn grant_role(ctx: Context<AGrantRole>, role: Role) -> Result<()> {
+    let role_state = &mut ctx.accounts.role_state;
+    role_state.role = role;
+    role_state.authority = ctx.accounts.authority.key();
+    role_state._bump = *ctx.bumps.get("role_state").unwrap();
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(role: Role)]
+pub struct AGrantRole<'info> {
+    #[account(
+        mut,
+        address = main_state.owner @ MyError::OnlyOwnerCanCall,
+    )]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_MAIN_STATE],
+        bump,
+    )]
+    pub main_state: Account<'info, MainState>,
+
+    ///CHECK: key receiving the role
+    pub authority: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [SEED_ROLE_STATE, role.seed(), authority.key().as_ref()],
+        bump,
+        space = 8 + RoleState::MAX_SIZE,
+    )]
+    pub role_state: Account<'info, RoleState>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,14 +1,19 @@
 use crate::{
     _main::main_state::{MainState, MainStateInput},
     constants::SEED_MAIN_STATE,
+    other_states::MetadataConstraints,
 };
 use anchor_lang::{prelude::*, Discriminator};
 
 pub fn init_main_state(ctx: Context<AInitMainState>, input: MainStateInput) -> Result<()> {
     let main_state = &mut ctx.accounts.main_state;
     let owner = ctx.accounts.owner.to_account_info();
+    input.validate()?;
     input.set_value(main_state);
+    main_state.set_default_lineage();
+    main_state.metadata_constraints = MetadataConstraints::new_default();
     main_state.owner = owner.key();
+    main_state.version = MainState::VERSION;
     main_state._bump = *ctx.bumps.get("main_state").unwrap();
 
     Ok(())
@@ -0,0 +1,69 @@
+use crate::{
+    _main::main_state::MainState,
+    constants::{LEGACY_PROFILE_BASE_URI, SEED_MAIN_STATE},
+    error::MyError,
+    other_states::{MetadataConstraints, UriPrefix},
+    utils::migrate_account,
+};
+use anchor_lang::{prelude::*, Discriminator};
+
+/// Grows an existing `MainState` to the current layout and stamps `MainState::VERSION`.
+/// Has to run before any other state can be migrated.
+pub fn migrate_main_state(ctx: Context<AMigrateMainState>) -> Result<()> {
+    let main_state = ctx.accounts.main_state.to_account_info();
+    {
+        // The old layout can't be deserialized yet, read the owner straight from the data
+        let data = main_state.try_borrow_data()?;
+        require!(
+            data.len() >= 8 + 32 && data[..8] == MainState::discriminator(),
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        let owner = Pubkey::try_from(&data[8..8 + 32]).unwrap();
+        require_keys_eq!(owner, ctx.accounts.owner.key(), MyError::OnlyOwnerCanCall);
+    }
+
+    migrate_account::<MainState>(
+        main_state,
+        ctx.accounts.owner.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        8 + MainState::MAX_SIZE,
+        |state| {
+            if state.version < 3 {
+                state.invitation_price_distribution = state.minting_cost_distribution;
+            }
+            if state.version < 4 {
+                state.set_default_lineage();
+            }
+            if state.version < 5 {
+                state.metadata_constraints = MetadataConstraints::new_default();
+            }
+            if state.version < 6 {
+                state.profile_base_uri = UriPrefix::new(LEGACY_PROFILE_BASE_URI).unwrap();
+            }
+            state.version = MainState::VERSION;
+        },
+    )?;
+
+    // distributions stored before they were validated
+    let main_state = Account::<MainState>::try_from(&ctx.accounts.main_state)?;
+    main_state.validate_distributions()?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AMigrateMainState<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    ///CHECK: checked in `migrate_main_state`
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [SEED_MAIN_STATE],
+        bump,
+    )]
+    pub main_state: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
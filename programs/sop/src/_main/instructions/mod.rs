@@ -7,9 +7,38 @@ pub use update_main_state::*;
 pub mod update_main_state_owner;
 pub use update_main_state_owner::*;
 
+pub mod grant_role;
+pub use grant_role::*;
+
+pub mod revoke_role;
+pub use revoke_role::*;
+
+pub mod pause;
+pub use pause::*;
+
+pub mod migrate_main_state;
+pub use migrate_main_state::*;
+
 pub mod set_native_collections;
 pub use set_native_collections::*;
 
-// check later
-pub mod reset_main;
-pub use reset_main::*;
+pub mod set_lineage_config;
+pub use set_lineage_config::*;
+
+pub mod set_invitation_quota;
+pub use set_invitation_quota::*;
+
+pub mod pricing;
+pub use pricing::*;
+
+pub mod payment;
+pub use payment::*;
+
+pub mod reset;
+pub use reset::*;
+
+pub mod set_metadata_constraints;
+pub use set_metadata_constraints::*;
+
+pub mod set_base_uris;
+pub use set_base_uris::*;
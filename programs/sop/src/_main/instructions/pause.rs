@@ -0,0 +1,49 @@
+use crate::{
+    _main::{
+        main_state::MainState,
+        role_state::{Role, RoleState},
+    },
+    constants::{PAUSE_ALL, SEED_MAIN_STATE, SEED_ROLE_STATE},
+    error::MyError,
+};
+use anchor_lang::prelude::*;
+
+/// `flags` is a bitset of the `PAUSE_*` constants
+pub fn pause(ctx: Context<APause>, flags: u8) -> Result<()> {
+    require!(flags & !PAUSE_ALL == 0, MyError::InvalidPauseFlags);
+    let main_state = &mut ctx.accounts.main_state;
+    main_state.paused |= flags;
+    Ok(())
+}
+
+pub fn unpause(ctx: Context<APause>, flags: u8) -> Result<()> {
+    require!(flags & !PAUSE_ALL == 0, MyError::InvalidPauseFlags);
+    let main_state = &mut ctx.accounts.main_state;
+    main_state.paused &= !flags;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct APause<'info> {
+    #[account(
+        constraint = main_state.is_authorized(
+            &pauser.key(),
+            &role_state,
+            Role::Pauser,
+        ) @ MyError::MissingRole,
+    )]
+    pub pauser: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_MAIN_STATE],
+        bump,
+    )]
+    pub main_state: Account<'info, MainState>,
+
+    #[account(
+        seeds = [SEED_ROLE_STATE, Role::Pauser.seed(), pauser.key().as_ref()],
+        bump = role_state._bump,
+    )]
+    pub role_state: Option<Box<Account<'info, RoleState>>>,
+}
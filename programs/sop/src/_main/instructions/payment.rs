@@ -0,0 +1,83 @@
+use crate::{
+    _main::{
+        main_state::MainState,
+        payment_state::{PaymentInput, PaymentState},
+        role_state::{Role, RoleState},
+    },
+    constants::{SEED_MAIN_STATE, SEED_PAYMENT_STATE, SEED_ROLE_STATE},
+    error::MyError,
+};
+use anchor_lang::prelude::*;
+
+pub fn init_payment_state(ctx: Context<AInitPaymentState>, input: PaymentInput) -> Result<()> {
+    let payment_state = &mut ctx.accounts.payment_state;
+    input.validate(&[])?;
+    input.set_value(payment_state);
+    payment_state._bump = *ctx.bumps.get("payment_state").unwrap();
+    Ok(())
+}
+
+pub fn update_payment_state(ctx: Context<AUpdatePaymentState>, input: PaymentInput) -> Result<()> {
+    let payment_state = &mut ctx.accounts.payment_state;
+    input.validate(&payment_state.mints)?;
+    input.set_value(payment_state);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AInitPaymentState<'info> {
+    #[account(
+        mut,
+        address = main_state.owner @ MyError::OnlyOwnerCanCall,
+    )]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_MAIN_STATE],
+        bump,
+    )]
+    pub main_state: Account<'info, MainState>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [SEED_PAYMENT_STATE],
+        bump,
+        space = 8 + PaymentState::MAX_SIZE,
+    )]
+    pub payment_state: Account<'info, PaymentState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AUpdatePaymentState<'info> {
+    #[account(
+        mut,
+        constraint = main_state.is_authorized(
+            &owner.key(),
+            &role_state,
+            Role::FeeManager,
+        ) @ MyError::MissingRole,
+    )]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_MAIN_STATE],
+        bump,
+    )]
+    pub main_state: Account<'info, MainState>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PAYMENT_STATE],
+        bump = payment_state._bump,
+    )]
+    pub payment_state: Account<'info, PaymentState>,
+
+    #[account(
+        seeds = [SEED_ROLE_STATE, Role::FeeManager.seed(), owner.key().as_ref()],
+        bump = role_state._bump,
+    )]
+    pub role_state: Option<Box<Account<'info, RoleState>>>,
+}
@@ -0,0 +1,83 @@
+use crate::{
+    _main::{
+        main_state::MainState,
+        pricing_state::{PricingInput, PricingState},
+        role_state::{Role, RoleState},
+    },
+    constants::{SEED_MAIN_STATE, SEED_PRICING_STATE, SEED_ROLE_STATE},
+    error::MyError,
+};
+use anchor_lang::prelude::*;
+
+pub fn init_pricing_state(ctx: Context<AInitPricingState>, input: PricingInput) -> Result<()> {
+    let pricing_state = &mut ctx.accounts.pricing_state;
+    input.validate()?;
+    input.set_value(pricing_state);
+    pricing_state._bump = *ctx.bumps.get("pricing_state").unwrap();
+    Ok(())
+}
+
+pub fn update_pricing_state(ctx: Context<AUpdatePricingState>, input: PricingInput) -> Result<()> {
+    let pricing_state = &mut ctx.accounts.pricing_state;
+    input.validate()?;
+    input.set_value(pricing_state);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AInitPricingState<'info> {
+    #[account(
+        mut,
+        address = main_state.owner @ MyError::OnlyOwnerCanCall,
+    )]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_MAIN_STATE],
+        bump,
+    )]
+    pub main_state: Account<'info, MainState>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [SEED_PRICING_STATE],
+        bump,
+        space = 8 + PricingState::MAX_SIZE,
+    )]
+    pub pricing_state: Account<'info, PricingState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AUpdatePricingState<'info> {
+    #[account(
+        mut,
+        constraint = main_state.is_authorized(
+            &owner.key(),
+            &role_state,
+            Role::FeeManager,
+        ) @ MyError::MissingRole,
+    )]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_MAIN_STATE],
+        bump,
+    )]
+    pub main_state: Account<'info, MainState>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PRICING_STATE],
+        bump = pricing_state._bump,
+    )]
+    pub pricing_state: Account<'info, PricingState>,
+
+    #[account(
+        seeds = [SEED_ROLE_STATE, Role::FeeManager.seed(), owner.key().as_ref()],
+        bump = role_state._bump,
+    )]
+    pub role_state: Option<Box<Account<'info, RoleState>>>,
+}
@@ -0,0 +1,303 @@
+use crate::{
+    _main::{main_state::MainState, PaymentState},
+    collection_factory::{CollectionState, CollectionType},
+    constants::{RESET_DELAY, SEED_COLLECTION_STATE, SEED_MAIN_STATE, SEED_PAYMENT_STATE},
+    error::MyError,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::get_associated_token_address, token::TokenAccount};
+use mpl_token_metadata::{
+    instruction::{
+        builders::Update, CollectionDetailsToggle, CollectionToggle, InstructionBuilder,
+        RuleSetToggle, UpdateArgs, UsesToggle,
+    },
+    state::{EDITION, PREFIX as METADATA},
+    ID as MPL_ID,
+};
+use solana_program::program::invoke_signed;
+
+/// Starts the decommission timelock, `execute_reset` can close `MainState` after `RESET_DELAY`.
+pub fn schedule_reset(ctx: Context<AScheduleReset>) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    main_state.reset_at = Clock::get()?.unix_timestamp + RESET_DELAY;
+    Ok(())
+}
+
+/// Only possible until the first collection authority is released.
+pub fn cancel_reset(ctx: Context<ACancelReset>) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    main_state.reset_at = 0;
+    Ok(())
+}
+
+/// Hands the update authority of a collection back to the owner, flags its `CollectionState`
+/// and drops it from the native collections of `MainState`. Every collection has to be
+/// released before `execute_reset`.
+pub fn release_collection_authority(ctx: Context<AReleaseCollectionAuthority>) -> Result<()> {
+    {
+        ctx.accounts.hand_back_update_authority()?;
+    }
+    {
+        let collection = ctx.accounts.collection.key();
+        let main_state = &mut ctx.accounts.main_state;
+        let native_types = [
+            CollectionType::Root,
+            CollectionType::Profile,
+            CollectionType::Badge,
+            CollectionType::ActivationToken,
+            CollectionType::Pass,
+        ]
+        .into_iter()
+        .filter(|collection_type| main_state.native_collection(*collection_type) == collection)
+        .collect::<Vec<_>>();
+        for collection_type in &native_types {
+            main_state.set_native_collection(*collection_type, System::id());
+        }
+
+        // collections made before `CollectionState` have none, they have to be native
+        let collection_state = &ctx.accounts.collection_state;
+        if collection_state.data_is_empty() {
+            require!(!native_types.is_empty(), MyError::UnknownNft);
+        } else {
+            let mut collection_state = Account::<CollectionState>::try_from(collection_state)?;
+            require!(
+                !collection_state.released,
+                MyError::CollectionAuthorityReleased
+            );
+            collection_state.released = true;
+            collection_state.exit(&crate::ID)?;
+            main_state.released_collections += 1;
+        }
+        main_state.collections_released = true;
+    }
+
+    Ok(())
+}
+
+/// `remaining_accounts` holds the `main_state` token account of every non native
+/// `PaymentState` mint, in order. They and the OPOS vault have to be claimed empty.
+pub fn execute_reset<'info>(ctx: Context<'_, '_, '_, 'info, AExecuteReset<'info>>) -> Result<()> {
+    let main_state = ctx.accounts.main_state.key();
+    let mut vaults = vec![(
+        ctx.accounts.main_state.opos_token,
+        ctx.accounts.opos_vault.to_account_info(),
+    )];
+    if let Some(payment_state) = &ctx.accounts.payment_state {
+        let payment_mints = payment_state
+            .mints
+            .iter()
+            .filter(|payment| !payment.is_native())
+            .collect::<Vec<_>>();
+        require!(
+            payment_mints.len() == ctx.remaining_accounts.len(),
+            MyError::InvalidPaymentAccounts
+        );
+        for (payment, vault) in payment_mints.into_iter().zip(ctx.remaining_accounts) {
+            vaults.push((payment.mint, vault.clone()));
+        }
+    }
+
+    for (mint, vault) in vaults {
+        require_keys_eq!(
+            vault.key(),
+            get_associated_token_address(&main_state, &mint),
+            MyError::InvalidPaymentAccounts
+        );
+        if vault.data_is_empty() {
+            continue;
+        }
+        let vault = Account::<TokenAccount>::try_from(&vault)?;
+        require!(vault.amount == 0, MyError::UnclaimedVaultBalance);
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AScheduleReset<'info> {
+    #[account(
+        mut,
+        address = main_state.owner @ MyError::OnlyOwnerCanCall,
+    )]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_MAIN_STATE],
+        bump,
+    )]
+    pub main_state: Account<'info, MainState>,
+}
+
+#[derive(Accounts)]
+pub struct ACancelReset<'info> {
+    #[account(
+        mut,
+        address = main_state.owner @ MyError::OnlyOwnerCanCall,
+    )]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_MAIN_STATE],
+        bump,
+        constraint = main_state.reset_at != 0 @ MyError::ResetNotScheduled,
+        constraint = !main_state.collections_released @ MyError::CollectionAuthorityReleased,
+    )]
+    pub main_state: Account<'info, MainState>,
+}
+
+#[derive(Accounts)]
+pub struct AReleaseCollectionAuthority<'info> {
+    #[account(
+        mut,
+        address = main_state.owner @ MyError::OnlyOwnerCanCall,
+    )]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_MAIN_STATE],
+        bump,
+        constraint = main_state.reset_at != 0 @ MyError::ResetNotScheduled,
+        constraint = Clock::get()?.unix_timestamp >= main_state.reset_at @ MyError::ResetTimelockActive,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    ///CHECK: the update authority CPI fails unless `main_state` holds it
+    pub collection: AccountInfo<'info>,
+
+    ///CHECK: flagged in `release_collection_authority`, empty for collections made before it
+    #[account(
+        mut,
+        seeds = [SEED_COLLECTION_STATE, collection.key().as_ref()],
+        bump,
+    )]
+    pub collection_state: AccountInfo<'info>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        seeds=[
+            METADATA.as_ref(),
+            MPL_ID.as_ref(),
+            collection.key().as_ref(),
+        ],
+        bump,
+        seeds::program = MPL_ID
+    )]
+    pub collection_metadata: AccountInfo<'info>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        seeds=[
+            METADATA.as_ref(),
+            MPL_ID.as_ref(),
+            collection.key().as_ref(),
+            EDITION.as_ref(),
+        ],
+        bump,
+        seeds::program = MPL_ID
+    )]
+    pub collection_edition: AccountInfo<'info>,
+
+    ///CHECK:
+    #[account()]
+    pub sysvar_instructions: AccountInfo<'info>,
+
+    ///CHECK:
+    #[account(address = MPL_ID)]
+    pub mpl_program: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AReleaseCollectionAuthority<'info> {
+    pub fn hand_back_update_authority(&mut self) -> Result<()> {
+        let mint = self.collection.to_account_info();
+        let metadata = self.collection_metadata.to_account_info();
+        let edition = self.collection_edition.to_account_info();
+        let payer = self.owner.to_account_info();
+        let mpl_program = self.mpl_program.to_account_info();
+        let system_program = self.system_program.to_account_info();
+        let sysvar_instructions = self.sysvar_instructions.to_account_info();
+        let main_state = &self.main_state;
+
+        let args = UpdateArgs::V1 {
+            new_update_authority: Some(payer.key()),
+            data: None,
+            primary_sale_happened: None,
+            is_mutable: None,
+            collection: CollectionToggle::None,
+            collection_details: CollectionDetailsToggle::None,
+            uses: UsesToggle::None,
+            rule_set: RuleSetToggle::None,
+            authorization_data: None,
+        };
+
+        let ix = Update {
+            mint: mint.key(),
+            metadata: metadata.key(),
+            edition: Some(edition.key()),
+            token: None,
+            payer: payer.key(),
+            args,
+            authority: main_state.key(),
+            delegate_record: None,
+            authorization_rules: None,
+            authorization_rules_program: None,
+            system_program: system_program.key(),
+            sysvar_instructions: sysvar_instructions.key(),
+        }
+        .instruction();
+
+        invoke_signed(
+            &ix,
+            &[
+                mint,
+                payer,
+                metadata,
+                edition,
+                mpl_program,
+                system_program,
+                sysvar_instructions,
+                main_state.to_account_info(),
+            ],
+            &[&[SEED_MAIN_STATE, &[main_state._bump]]],
+        )?;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct AExecuteReset<'info> {
+    #[account(
+        mut,
+        address = main_state.owner @ MyError::OnlyOwnerCanCall,
+    )]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [SEED_MAIN_STATE],
+        bump,
+        constraint = main_state.reset_at != 0 @ MyError::ResetNotScheduled,
+        constraint = Clock::get()?.unix_timestamp >= main_state.reset_at @ MyError::ResetTimelockActive,
+        constraint = main_state.collections_handed_back() @ MyError::CollectionAuthorityNotReleased,
+        constraint = main_state.escrowed_sol == 0 @ MyError::UnclaimedPaymentEarnings,
+    )]
+    pub main_state: Account<'info, MainState>,
+
+    ///CHECK: checked in `execute_reset`, the `main_state` OPOS token account
+    pub opos_vault: AccountInfo<'info>,
+
+    #[account(
+        seeds = [SEED_PAYMENT_STATE],
+        bump = payment_state._bump,
+    )]
+    pub payment_state: Option<Box<Account<'info, PaymentState>>>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,16 +1,12 @@
 use crate::{
-    _main::main_state::{MainState, MainStateInput},
-    constants::SEED_MAIN_STATE,
+    _main::{main_state::MainState, role_state::RoleState},
+    constants::{SEED_MAIN_STATE, SEED_ROLE_STATE},
     error::MyError,
 };
 use anchor_lang::prelude::*;
 
-// pub fn reset_main(ctx:Context<AResetMain>)->Result<()>{
-//     Ok(())
-// }
-
 #[derive(Accounts)]
-pub struct AResetMain<'info> {
+pub struct ARevokeRole<'info> {
     #[account(
         mut,
         address = main_state.owner @ MyError::OnlyOwnerCanCall,
@@ -18,12 +14,16 @@ pub struct AResetMain<'info> {
     pub owner: Signer<'info>,
 
     #[account(
-        mut,
-        close = owner,
         seeds = [SEED_MAIN_STATE],
         bump,
     )]
     pub main_state: Account<'info, MainState>,
 
-    pub system_program: Program<'info, System>,
+    #[account(
+        mut,
+        close = owner,
+        seeds = [SEED_ROLE_STATE, role_state.role.seed(), role_state.authority.as_ref()],
+        bump = role_state._bump,
+    )]
+    pub role_state: Account<'info, RoleState>,
 }
@@ -0,0 +1,58 @@
+use crate::{
+    _main::{
+        main_state::MainState,
+        role_state::{Role, RoleState},
+    },
+    constants::{SEED_MAIN_STATE, SEED_ROLE_STATE},
+    error::MyError,
+    other_states::UriPrefix,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone)]
+pub struct BaseUrisInput {
+    pub profile: String,
+    pub activation_token: String,
+    pub collection: String,
+}
+
+impl BaseUrisInput {
+    pub fn set_value(&self, state: &mut MainState) -> Result<()> {
+        state.profile_base_uri = UriPrefix::new(&self.profile)?;
+        state.activation_token_base_uri = UriPrefix::new(&self.activation_token)?;
+        state.collection_base_uri = UriPrefix::new(&self.collection)?;
+        Ok(())
+    }
+}
+
+pub fn set_base_uris(ctx: Context<ASetBaseUris>, input: BaseUrisInput) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    input.set_value(main_state)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASetBaseUris<'info> {
+    #[account(
+        mut,
+        constraint = main_state.is_authorized(
+            &owner.key(),
+            &role_state,
+            Role::CollectionManager,
+        ) @ MyError::MissingRole,
+    )]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_MAIN_STATE],
+        bump,
+    )]
+    pub main_state: Account<'info, MainState>,
+
+    #[account(
+        seeds = [SEED_ROLE_STATE, Role::CollectionManager.seed(), owner.key().as_ref()],
+        bump = role_state._bump,
+    )]
+    pub role_state: Option<Box<Account<'info, RoleState>>>,
+}
@@ -0,0 +1,69 @@
+use crate::{
+    _main::{
+        main_state::MainState,
+        role_state::{Role, RoleState},
+    },
+    constants::{SEED_MAIN_STATE, SEED_ROLE_STATE},
+    error::MyError,
+    other_states::InvitationQuota,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone)]
+pub struct InvitationQuotaInput {
+    /// `0` disables each limit
+    pub max_per_profile: u64,
+    pub max_per_generation: u64,
+    /// window length in seconds, needed when `max_per_window` is set
+    pub window: i64,
+    pub max_per_window: u64,
+}
+
+impl InvitationQuotaInput {
+    pub fn set_value(&self, state: &mut MainState) -> Result<()> {
+        let quota = InvitationQuota {
+            max_per_profile: self.max_per_profile,
+            max_per_generation: self.max_per_generation,
+            window: self.window,
+            max_per_window: self.max_per_window,
+        };
+        quota.validate()?;
+        state.invitation_quota = quota;
+        Ok(())
+    }
+}
+
+pub fn set_invitation_quota(
+    ctx: Context<ASetInvitationQuota>,
+    input: InvitationQuotaInput,
+) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    input.set_value(main_state)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASetInvitationQuota<'info> {
+    #[account(
+        mut,
+        constraint = main_state.is_authorized(
+            &owner.key(),
+            &role_state,
+            Role::FeeManager,
+        ) @ MyError::MissingRole,
+    )]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_MAIN_STATE],
+        bump,
+    )]
+    pub main_state: Account<'info, MainState>,
+
+    #[account(
+        seeds = [SEED_ROLE_STATE, Role::FeeManager.seed(), owner.key().as_ref()],
+        bump = role_state._bump,
+    )]
+    pub role_state: Option<Box<Account<'info, RoleState>>>,
+}
@@ -0,0 +1,57 @@
+use crate::{
+    _main::{
+        main_state::MainState,
+        role_state::{Role, RoleState},
+    },
+    constants::{SEED_MAIN_STATE, SEED_ROLE_STATE},
+    error::MyError,
+    other_states::LineageDistribution,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone)]
+pub struct LineageConfigInput {
+    /// share of each ancestor, parent first. Its length is the new lineage depth
+    pub ancestors: Vec<u16>,
+    pub genesis: u16,
+}
+
+impl LineageConfigInput {
+    pub fn set_value(&self, state: &mut MainState) -> Result<()> {
+        state.lineage_distribution = LineageDistribution::new(&self.ancestors, self.genesis)?;
+        state.lineage_depth = self.ancestors.len() as u8;
+        Ok(())
+    }
+}
+
+pub fn set_lineage_config(ctx: Context<ASetLineageConfig>, input: LineageConfigInput) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    input.set_value(main_state)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASetLineageConfig<'info> {
+    #[account(
+        mut,
+        constraint = main_state.is_authorized(
+            &owner.key(),
+            &role_state,
+            Role::FeeManager,
+        ) @ MyError::MissingRole,
+    )]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_MAIN_STATE],
+        bump,
+    )]
+    pub main_state: Account<'info, MainState>,
+
+    #[account(
+        seeds = [SEED_ROLE_STATE, Role::FeeManager.seed(), owner.key().as_ref()],
+        bump = role_state._bump,
+    )]
+    pub role_state: Option<Box<Account<'info, RoleState>>>,
+}
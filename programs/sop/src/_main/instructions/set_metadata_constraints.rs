@@ -0,0 +1,61 @@
+use crate::{
+    _main::{
+        main_state::MainState,
+        role_state::{Role, RoleState},
+    },
+    constants::{SEED_MAIN_STATE, SEED_ROLE_STATE},
+    error::MyError,
+    other_states::MetadataConstraints,
+};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone)]
+pub struct MetadataConstraintsInput {
+    pub max_name_len: u8,
+    pub max_symbol_len: u8,
+    /// at most `MAX_URI_PREFIXES`, empty disables uri changes
+    pub uri_prefixes: Vec<String>,
+}
+
+impl MetadataConstraintsInput {
+    pub fn set_value(&self, state: &mut MainState) -> Result<()> {
+        state.metadata_constraints =
+            MetadataConstraints::new(self.max_name_len, self.max_symbol_len, &self.uri_prefixes)?;
+        Ok(())
+    }
+}
+
+pub fn set_metadata_constraints(
+    ctx: Context<ASetMetadataConstraints>,
+    input: MetadataConstraintsInput,
+) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    input.set_value(main_state)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASetMetadataConstraints<'info> {
+    #[account(
+        mut,
+        constraint = main_state.is_authorized(
+            &owner.key(),
+            &role_state,
+            Role::CollectionManager,
+        ) @ MyError::MissingRole,
+    )]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_MAIN_STATE],
+        bump,
+    )]
+    pub main_state: Account<'info, MainState>,
+
+    #[account(
+        seeds = [SEED_ROLE_STATE, Role::CollectionManager.seed(), owner.key().as_ref()],
+        bump = role_state._bump,
+    )]
+    pub role_state: Option<Box<Account<'info, RoleState>>>,
+}
@@ -1,15 +1,104 @@
 use crate::{
-    _main::main_state::{MainState, MainStateInput},
-    constants::SEED_MAIN_STATE,
+    _main::{
+        main_state::MainState,
+        role_state::{Role, RoleState},
+    },
+    collection_factory::CollectionType,
+    constants::{SEED_MAIN_STATE, SEED_ROLE_STATE},
     error::MyError,
 };
 use anchor_lang::prelude::*;
+use mpl_token_metadata::{
+    state::{Metadata, TokenMetadataAccount, PREFIX as METADATA},
+    ID as MPL_ID,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Copy)]
+pub struct NativeCollectionsInput {
+    pub root: Pubkey,
+    pub profile: Pubkey,
+    pub badge: Pubkey,
+    pub activation_token: Pubkey,
+    pub pass: Pubkey,
+}
+
+impl NativeCollectionsInput {
+    /// collections to register, `System::id()` leaves a slot as it is
+    pub fn entries(&self) -> Vec<(CollectionType, Pubkey)> {
+        [
+            (CollectionType::Root, self.root),
+            (CollectionType::Profile, self.profile),
+            (CollectionType::Badge, self.badge),
+            (CollectionType::ActivationToken, self.activation_token),
+            (CollectionType::Pass, self.pass),
+        ]
+        .into_iter()
+        .filter(|(_, collection)| *collection != System::id())
+        .collect()
+    }
+
+    /// registered slots are only cleared by `release_collection_authority`
+    pub fn validate(&self, state: &MainState) -> Result<()> {
+        for (collection_type, _) in self.entries() {
+            require_keys_eq!(
+                state.native_collection(collection_type),
+                System::id(),
+                MyError::AlreadySet
+            );
+        }
+        Ok(())
+    }
+
+    pub fn set_value(&self, state: &mut MainState) {
+        for (collection_type, collection) in self.entries() {
+            state.set_native_collection(collection_type, collection);
+        }
+    }
+}
+
+/// Registers collections in unset native slots. `remaining_accounts` holds the metadata of
+/// each registered collection, in the order of `NativeCollectionsInput`, `main_state` has to
+/// be its update authority.
+pub fn set_native_collections<'info>(
+    ctx: Context<'_, '_, '_, 'info, ASetNativeCollection<'info>>,
+    input: NativeCollectionsInput,
+) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    input.validate(main_state)?;
+
+    let entries = input.entries();
+    require!(
+        entries.len() == ctx.remaining_accounts.len(),
+        MyError::UnknownNft
+    );
+    for ((_, collection), metadata_info) in entries.iter().zip(ctx.remaining_accounts) {
+        let (metadata_key, _) = Pubkey::find_program_address(
+            &[METADATA.as_ref(), MPL_ID.as_ref(), collection.as_ref()],
+            &MPL_ID,
+        );
+        require_keys_eq!(metadata_info.key(), metadata_key, MyError::UnknownNft);
+        let metadata =
+            Metadata::from_account_info(metadata_info).map_err(|_| MyError::UnknownNft)?;
+        require_keys_eq!(
+            metadata.update_authority,
+            main_state.key(),
+            MyError::UnknownNft
+        );
+    }
+
+    input.set_value(main_state);
+    Ok(())
+}
 
 #[derive(Accounts)]
 pub struct ASetNativeCollection<'info> {
     #[account(
         mut,
-        address = main_state.owner @ MyError::OnlyOwnerCanCall,
+        constraint = main_state.is_authorized(
+            &owner.key(),
+            &role_state,
+            Role::CollectionManager,
+        ) @ MyError::MissingRole,
     )]
     pub owner: Signer<'info>,
 
@@ -17,6 +106,13 @@ pub struct ASetNativeCollection<'info> {
         mut,
         seeds = [SEED_MAIN_STATE],
         bump,
+        constraint = main_state.reset_at == 0 @ MyError::ResetScheduled,
     )]
     pub main_state: Account<'info, MainState>,
+
+    #[account(
+        seeds = [SEED_ROLE_STATE, Role::CollectionManager.seed(), owner.key().as_ref()],
+        bump = role_state._bump,
+    )]
+    pub role_state: Option<Box<Account<'info, RoleState>>>,
 }
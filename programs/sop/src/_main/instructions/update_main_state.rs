@@ -1,12 +1,16 @@
 use crate::{
-    _main::main_state::{MainState, MainStateInput},
-    constants::SEED_MAIN_STATE,
+    _main::{
+        main_state::{MainState, MainStateInput},
+        role_state::{Role, RoleState},
+    },
+    constants::{SEED_MAIN_STATE, SEED_ROLE_STATE},
     error::MyError,
 };
 use anchor_lang::prelude::*;
 
 pub fn update_main_state(ctx: Context<AUpdateMainState>, input: MainStateInput) -> Result<()> {
     let main_state = &mut ctx.accounts.main_state;
+    input.validate()?;
     input.set_value(main_state);
     Ok(())
 }
@@ -15,7 +19,11 @@ pub fn update_main_state(ctx: Context<AUpdateMainState>, input: MainStateInput)
 pub struct AUpdateMainState<'info> {
     #[account(
         mut,
-        address = main_state.owner @ MyError::OnlyOwnerCanCall,
+        constraint = main_state.is_authorized(
+            &owner.key(),
+            &role_state,
+            Role::FeeManager,
+        ) @ MyError::MissingRole,
     )]
     pub owner: Signer<'info>,
 
@@ -25,4 +33,10 @@ pub struct AUpdateMainState<'info> {
         bump,
     )]
     pub main_state: Account<'info, MainState>,
+
+    #[account(
+        seeds = [SEED_ROLE_STATE, Role::FeeManager.seed(), owner.key().as_ref()],
+        bump = role_state._bump,
+    )]
+    pub role_state: Option<Box<Account<'info, RoleState>>>,
 }
@@ -1,12 +1,29 @@
 use crate::{_main::main_state::MainState, constants::SEED_MAIN_STATE, error::MyError};
 use anchor_lang::prelude::*;
 
+/// First step of the ownership transfer: `new_owner` still has to call
+/// `accept_main_state_ownership` before it becomes the owner.
 pub fn update_main_state_owner(
     ctx: Context<AUpdateMainStateOwner>,
     new_owner: Pubkey,
 ) -> Result<()> {
     let program_state = &mut ctx.accounts.main_state;
-    program_state.owner = new_owner;
+    program_state.pending_owner = new_owner;
+
+    Ok(())
+}
+
+pub fn accept_main_state_ownership(ctx: Context<AAcceptMainStateOwnership>) -> Result<()> {
+    let program_state = &mut ctx.accounts.main_state;
+    program_state.owner = program_state.pending_owner;
+    program_state.pending_owner = System::id();
+
+    Ok(())
+}
+
+pub fn cancel_ownership_transfer(ctx: Context<AUpdateMainStateOwner>) -> Result<()> {
+    let program_state = &mut ctx.accounts.main_state;
+    program_state.pending_owner = System::id();
 
     Ok(())
 }
@@ -26,3 +43,19 @@ pub struct AUpdateMainStateOwner<'info> {
     )]
     pub main_state: Account<'info, MainState>,
 }
+
+#[derive(Accounts)]
+pub struct AAcceptMainStateOwnership<'info> {
+    #[account(
+        mut,
+        address = main_state.pending_owner @ MyError::OnlyPendingOwnerCanCall,
+    )]
+    pub new_owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_MAIN_STATE],
+        bump,
+    )]
+    pub main_state: Account<'info, MainState>,
+}
@@ -2,14 +2,22 @@ use anchor_lang::prelude::*;
 use anchor_lang::{AnchorDeserialize, AnchorSerialize};
 use mpl_token_metadata::state::{Metadata, TokenMetadataAccount};
 
+use crate::_main::role_state::{Role, RoleState};
+use crate::collection_factory::CollectionType;
+use crate::constants::MAX_LINEAGE_DEPTH;
 use crate::error::MyError;
-use crate::other_states::{MintingCostDistribution, TradingPriceDistribution};
+use crate::other_states::{
+    InvitationQuota, LineageDistribution, MetadataConstraints, MintingCostDistribution,
+    TradingPriceDistribution, UriPrefix,
+};
 
 #[account]
 pub struct MainState {
     pub owner: Pubkey,
     pub opos_token: Pubkey,
+    /// superseded by `PricingState`
     pub profile_minting_cost: u64,
+    /// superseded by `PricingState`
     pub invitation_minting_cost: u64,
     pub minting_cost_distribution: MintingCostDistribution,
     pub trading_price_distribution: TradingPriceDistribution,
@@ -19,10 +27,158 @@ pub struct MainState {
     pub profile_collection: Pubkey,
     pub genesis_profile: Pubkey,
     pub common_lut: Pubkey,
+    /// proposed by `update_main_state_owner`, becomes `owner` once it accepts
+    pub pending_owner: Pubkey,
+    /// bitset of `PAUSE_*` flags
+    pub paused: u8,
+    // native collections (the profile one is `profile_collection`)
+    pub root_collection: Pubkey,
+    pub badge_collection: Pubkey,
+    pub activation_token_collection: Pubkey,
+    pub pass_collection: Pubkey,
+    /// layout version, bumped by `migrate_main_state`
+    pub version: u8,
+    /// unix time after which `execute_reset` can close this state, `0` when not scheduled
+    pub reset_at: i64,
+    /// bounds for the price a profile holder can set with `set_invitation_price`
+    pub min_invitation_price: u64,
+    pub max_invitation_price: u64,
+    /// superseded by `lineage_distribution`
+    pub invitation_price_distribution: MintingCostDistribution,
+    /// number of ancestors paid on profile mints, invitations and trades, at most `MAX_LINEAGE_DEPTH`
+    pub lineage_depth: u8,
+    /// split of the profile minting cost, invitation price and trading royalties, supersedes
+    /// `minting_cost_distribution` and `invitation_price_distribution`
+    pub lineage_distribution: LineageDistribution,
+    /// limits on what holders can set with `update_profile_metadata`
+    pub metadata_constraints: MetadataConstraints,
+    /// uris are composed on-chain as base uri + uri hash
+    pub profile_base_uri: UriPrefix,
+    pub activation_token_base_uri: UriPrefix,
+    pub collection_base_uri: UriPrefix,
+    /// limits on `mint_activation_token`, none by default
+    pub invitation_quota: InvitationQuota,
+    /// set once `release_collection_authority` ran, the reset can't be cancelled anymore
+    pub collections_released: bool,
+    /// native SOL paid for profiles and held on this account until claimed
+    pub escrowed_sol: u64,
+    /// collections made by `create_collection`, or counted by `migrate_collection_state`
+    pub total_collections: u64,
+    /// collections whose update authority `release_collection_authority` handed back
+    pub released_collections: u64,
 }
 
 impl MainState {
     pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
+    pub const VERSION: u8 = 9;
+
+    pub fn native_collection(&self, collection_type: CollectionType) -> Pubkey {
+        match collection_type {
+            CollectionType::Root => self.root_collection,
+            CollectionType::Profile => self.profile_collection,
+            CollectionType::Badge => self.badge_collection,
+            CollectionType::ActivationToken => self.activation_token_collection,
+            CollectionType::Pass => self.pass_collection,
+        }
+    }
+
+    pub fn set_native_collection(&mut self, collection_type: CollectionType, collection: Pubkey) {
+        match collection_type {
+            CollectionType::Root => self.root_collection = collection,
+            CollectionType::Profile => self.profile_collection = collection,
+            CollectionType::Badge => self.badge_collection = collection,
+            CollectionType::ActivationToken => self.activation_token_collection = collection,
+            CollectionType::Pass => self.pass_collection = collection,
+        }
+    }
+
+    /// every collection update authority held by this state has been handed back to the
+    /// owner. Native slots are only cleared by `release_collection_authority`
+    pub fn collections_handed_back(&self) -> bool {
+        let native_released = [
+            self.root_collection,
+            self.profile_collection,
+            self.badge_collection,
+            self.activation_token_collection,
+            self.pass_collection,
+        ]
+        .iter()
+        .all(|collection| *collection == System::id());
+        native_released && self.released_collections == self.total_collections
+    }
+
+    /// `custom_price` set by the inviter (kept within the current bounds), else `default_price`.
+    /// Without bounds, as after `migrate_main_state`, custom prices are ignored
+    pub fn invitation_price(&self, custom_price: u64, default_price: u64) -> u64 {
+        if custom_price == 0 || self.max_invitation_price == 0 {
+            return default_price;
+        }
+        custom_price.clamp(self.min_invitation_price, self.max_invitation_price)
+    }
+
+    /// the first `lineage_depth` of `ancestors` then genesis, each with its share of `amount`
+    pub fn lineage_split(
+        &self,
+        ancestors: &[Pubkey; MAX_LINEAGE_DEPTH],
+        amount: u64,
+    ) -> Result<Vec<(Pubkey, u64)>> {
+        require!(
+            ancestors[0] != Pubkey::default(),
+            MyError::LineageNotMigrated
+        );
+        let depth = self.lineage_depth as usize;
+        let (ancestor_shares, genesis_share) =
+            self.lineage_distribution.split_cost(amount, depth)?;
+        Ok(ancestors[..depth]
+            .iter()
+            .copied()
+            .zip(ancestor_shares)
+            .chain([(self.genesis_profile, genesis_share)])
+            .collect())
+    }
+
+    pub fn validate_distributions(&self) -> Result<()> {
+        self.minting_cost_distribution.validate()?;
+        self.trading_price_distribution.validate()?;
+        self.invitation_price_distribution.validate()?;
+        self.lineage_distribution
+            .validate(self.lineage_depth as usize)
+    }
+
+    /// four levels deep, as the lineage was before it became configurable
+    pub fn set_default_lineage(&mut self) {
+        self.lineage_depth = 4;
+        self.lineage_distribution = self.minting_cost_distribution.into();
+    }
+
+    pub fn profile_uri(&self, uri_hash: &str) -> Result<String> {
+        self.profile_base_uri.join(uri_hash)
+    }
+
+    pub fn activation_token_uri(&self, uri_hash: &str) -> Result<String> {
+        self.activation_token_base_uri.join(uri_hash)
+    }
+
+    pub fn collection_uri(&self, uri_hash: &str) -> Result<String> {
+        self.collection_base_uri.join(uri_hash)
+    }
+
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.paused & flag != 0
+    }
+
+    /// `authority` is the owner or has been granted `role`
+    pub fn is_authorized(
+        &self,
+        authority: &Pubkey,
+        role_state: &Option<Box<Account<RoleState>>>,
+        role: Role,
+    ) -> bool {
+        *authority == self.owner
+            || role_state
+                .as_ref()
+                .map_or(false, |state| state.role == role && state.authority == *authority)
+    }
     // pub fn verify_profile<'info>(&self, metadata_account_info: &'info AccountInfo) -> Result<()> {
     //     let metadata =
     //         Metadata::from_account_info(metadata_account_info).map_err(|_| MyError::UnknownNft)?;
@@ -52,10 +208,24 @@ pub struct MainStateInput {
     pub opos_token: Pubkey,
     pub minting_cost_distribution: MintingCostDistribution,
     pub trading_price_distribution: TradingPriceDistribution,
+    pub min_invitation_price: u64,
+    pub max_invitation_price: u64,
+    pub invitation_price_distribution: MintingCostDistribution,
     // pub activation_token_collection_id: Pubkey,
 }
 
 impl MainStateInput {
+    pub fn validate(&self) -> Result<()> {
+        self.minting_cost_distribution.validate()?;
+        self.trading_price_distribution.validate()?;
+        self.invitation_price_distribution.validate()?;
+        require!(
+            self.min_invitation_price <= self.max_invitation_price,
+            MyError::InvalidInvitationPriceBounds
+        );
+        Ok(())
+    }
+
     pub fn set_value(&self, mut state: &mut MainState) {
         // state.activation_token_collection_id = self.activation_token_collection_id;
         state.opos_token = self.opos_token;
@@ -63,5 +233,27 @@ impl MainStateInput {
         state.trading_price_distribution = self.trading_price_distribution;
         state.profile_minting_cost = self.profile_minting_cost;
         state.invitation_minting_cost = self.invitation_minting_cost;
+        state.min_invitation_price = self.min_invitation_price;
+        state.max_invitation_price = self.max_invitation_price;
+        state.invitation_price_distribution = self.invitation_price_distribution;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_invitation_price_needs_bounds() {
+        let mut main_state = MainState::deserialize(&mut &[0u8; MainState::MAX_SIZE][..]).unwrap();
+        assert_eq!(main_state.invitation_price(0, 50), 50);
+        assert_eq!(main_state.invitation_price(10, 50), 50);
+
+        main_state.min_invitation_price = 20;
+        main_state.max_invitation_price = 100;
+        assert_eq!(main_state.invitation_price(0, 50), 50);
+        assert_eq!(main_state.invitation_price(10, 50), 20);
+        assert_eq!(main_state.invitation_price(70, 50), 70);
+        assert_eq!(main_state.invitation_price(200, 50), 100);
     }
 }
@@ -1,5 +1,11 @@
 pub mod instructions;
 pub mod main_state;
+pub mod payment_state;
+pub mod pricing_state;
+pub mod role_state;
 
 pub use instructions::*;
 pub use main_state::{MainState, MainStateInput};
+pub use payment_state::{PaymentInput, PaymentMint, PaymentState};
+pub use pricing_state::{PriceTier, PricingInput, PricingState};
+pub use role_state::{Role, RoleState};
@@ -0,0 +1,222 @@
+use anchor_lang::prelude::*;
+use anchor_lang::{AnchorDeserialize, AnchorSerialize};
+use anchor_spl::token::{spl_token::native_mint, Mint, TokenAccount};
+
+use crate::{
+    constants::{MAX_PAYMENT_MINTS, PAYMENT_RATE_SCALE},
+    curve::{target_amount_for_base, CurveV0, TokenBondingV0},
+    error::MyError,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Copy, PartialEq, Debug)]
+pub struct PaymentMint {
+    /// `native_mint::ID` stands for native SOL
+    pub mint: Pubkey,
+    /// `mint` base units charged per OPOS base unit, scaled by `PAYMENT_RATE_SCALE`
+    pub rate: u64,
+    /// curve with OPOS as base and `mint` as target. When set the charge is what the OPOS
+    /// cost buys on it and `rate` is ignored
+    pub token_bonding: Pubkey,
+    /// no longer accepted, entries are never removed so earnings indexes stay valid
+    pub disabled: bool,
+}
+
+impl PaymentMint {
+    pub fn is_native(&self) -> bool {
+        self.mint == native_mint::ID
+    }
+
+    pub fn priced_by_curve(&self) -> bool {
+        self.token_bonding != System::id()
+    }
+
+    /// amount of `mint` charged for `opos_cost`, `bought_on_curve` being what `opos_cost`
+    /// buys on `token_bonding`
+    pub fn cost(&self, opos_cost: u64, bought_on_curve: Option<u64>) -> Result<u64> {
+        if self.priced_by_curve() {
+            return bought_on_curve.ok_or_else(|| error!(MyError::InvalidPaymentAccounts));
+        }
+        let cost = opos_cost as u128 * self.rate as u128 / PAYMENT_RATE_SCALE as u128;
+        u64::try_from(cost).map_err(|_| error!(MyError::InvalidPaymentMints))
+    }
+
+    /// whether the payer accepts being charged `cost`. Curve prices move with every trade in
+    /// the same block, so paying in a curve priced mint needs a `max_payment`
+    pub fn check_max_payment(&self, cost: u64, max_payment: Option<u64>) -> Result<()> {
+        match max_payment {
+            Some(max_payment) => require!(cost <= max_payment, MyError::MaxPaymentExceeded),
+            None => require!(!self.priced_by_curve(), MyError::MaxPaymentExceeded),
+        }
+        Ok(())
+    }
+}
+
+/// Mints accepted besides OPOS for profiles and invitations, priced against the OPOS costs
+/// of `PricingState`
+#[account]
+pub struct PaymentState {
+    pub _bump: u8,
+    pub mints: Vec<PaymentMint>,
+}
+
+impl PaymentState {
+    pub const MAX_SIZE: usize = 1 + 4 + MAX_PAYMENT_MINTS * std::mem::size_of::<PaymentMint>();
+
+    /// index and entry of `mint`, as long as it is still accepted
+    pub fn accepted(&self, mint: &Pubkey) -> Result<(usize, PaymentMint)> {
+        self.mints
+            .iter()
+            .position(|payment| payment.mint == *mint && !payment.disabled)
+            .map(|index| (index, self.mints[index]))
+            .ok_or_else(|| error!(MyError::PaymentMintNotAccepted))
+    }
+
+    /// index and entry of `payment_mint` and the amount of it charged for `opos_cost`, at most
+    /// `max_payment`. Curve priced mints need the bonding, its curve and base storage
+    #[allow(clippy::too_many_arguments)]
+    pub fn charge<'info>(
+        &self,
+        payment_mint: &Account<'info, Mint>,
+        opos_mint: &Account<'info, Mint>,
+        opos_cost: u64,
+        max_payment: Option<u64>,
+        token_bonding: Option<&Account<'info, TokenBondingV0>>,
+        curve: Option<&Account<'info, CurveV0>>,
+        base_storage: Option<&Account<'info, TokenAccount>>,
+    ) -> Result<(usize, PaymentMint, u64)> {
+        let (index, payment) = self.accepted(&payment_mint.key())?;
+        let bought_on_curve = match (token_bonding, curve, base_storage) {
+            (Some(token_bonding), Some(curve), Some(base_storage)) if payment.priced_by_curve() => {
+                require!(
+                    token_bonding.key() == payment.token_bonding
+                        && token_bonding.base_mint == opos_mint.key()
+                        && token_bonding.target_mint == payment.mint
+                        && token_bonding.curve == curve.key()
+                        && token_bonding.base_storage == base_storage.key(),
+                    MyError::InvalidPaymentAccounts
+                );
+                Some(target_amount_for_base(
+                    token_bonding,
+                    curve,
+                    base_storage,
+                    opos_mint,
+                    payment_mint,
+                    opos_cost,
+                    Clock::get()?.unix_timestamp,
+                )?)
+            }
+            _ => None,
+        };
+        let cost = payment.cost(opos_cost, bought_on_curve)?;
+        payment.check_max_payment(cost, max_payment)?;
+        Ok((index, payment, cost))
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone)]
+pub struct PaymentInput {
+    pub mints: Vec<PaymentMint>,
+}
+
+impl PaymentInput {
+    /// `current` entries keep their place, new mints are appended
+    pub fn validate(&self, current: &[PaymentMint]) -> Result<()> {
+        require!(
+            self.mints.len() <= MAX_PAYMENT_MINTS && self.mints.len() >= current.len(),
+            MyError::InvalidPaymentMints
+        );
+        require!(
+            current
+                .iter()
+                .zip(&self.mints)
+                .all(|(old, new)| old.mint == new.mint),
+            MyError::InvalidPaymentMints
+        );
+        for (index, payment) in self.mints.iter().enumerate() {
+            require!(
+                payment.rate > 0 || payment.priced_by_curve(),
+                MyError::InvalidPaymentMints
+            );
+            require!(
+                self.mints[..index]
+                    .iter()
+                    .all(|seen| seen.mint != payment.mint),
+                MyError::InvalidPaymentMints
+            );
+        }
+        Ok(())
+    }
+
+    pub fn set_value(&self, state: &mut PaymentState) {
+        state.mints = self.mints.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payment(mint: Pubkey, rate: u64) -> PaymentMint {
+        PaymentMint {
+            mint,
+            rate,
+            token_bonding: Pubkey::default(),
+            disabled: false,
+        }
+    }
+
+    #[test]
+    fn charges_by_rate_or_curve() {
+        let usdc = payment(Pubkey::new_unique(), PAYMENT_RATE_SCALE / 4);
+        assert_eq!(usdc.cost(1_000, None).unwrap(), 250);
+
+        let curve_priced = PaymentMint {
+            token_bonding: Pubkey::new_unique(),
+            ..payment(Pubkey::new_unique(), 0)
+        };
+        assert_eq!(curve_priced.cost(1_000, Some(42)).unwrap(), 42);
+        assert!(curve_priced.cost(1_000, None).is_err());
+    }
+
+    #[test]
+    fn curve_prices_need_a_max_payment() {
+        let usdc = payment(Pubkey::new_unique(), PAYMENT_RATE_SCALE / 4);
+        assert!(usdc.check_max_payment(250, None).is_ok());
+        assert!(usdc.check_max_payment(250, Some(249)).is_err());
+
+        let curve_priced = PaymentMint {
+            token_bonding: Pubkey::new_unique(),
+            ..payment(Pubkey::new_unique(), 0)
+        };
+        assert!(curve_priced.check_max_payment(42, None).is_err());
+        assert!(curve_priced.check_max_payment(42, Some(42)).is_ok());
+        assert!(curve_priced.check_max_payment(43, Some(42)).is_err());
+    }
+
+    #[test]
+    fn only_appends_new_mints() {
+        let (a, b) = (
+            payment(Pubkey::new_unique(), 1),
+            payment(Pubkey::new_unique(), 1),
+        );
+        let input = |mints| PaymentInput { mints };
+        assert!(input(vec![a, b]).validate(&[a]).is_ok());
+        assert!(input(vec![b, a]).validate(&[a]).is_err());
+        assert!(input(vec![]).validate(&[a]).is_err());
+        assert!(input(vec![a, a]).validate(&[]).is_err());
+        assert!(input(vec![payment(a.mint, 0)]).validate(&[]).is_err());
+
+        let state = PaymentState {
+            _bump: 0,
+            mints: vec![
+                a,
+                PaymentMint {
+                    disabled: true,
+                    ..b
+                },
+            ],
+        };
+        assert_eq!(state.accepted(&a.mint).unwrap(), (0, a));
+        assert!(state.accepted(&b.mint).is_err());
+    }
+}
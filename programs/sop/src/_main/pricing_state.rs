@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_lang::{AnchorDeserialize, AnchorSerialize};
+
+use crate::error::MyError;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Copy)]
+pub struct PriceTier {
+    /// first generation this tier applies to, it runs until the next tier starts
+    pub from_generation: u64,
+    pub profile_minting_cost: u64,
+    pub invitation_minting_cost: u64,
+}
+
+/// Minting costs by generation, replaces the flat costs on `MainState`
+#[account]
+pub struct PricingState {
+    pub _bump: u8,
+    pub tiers: Vec<PriceTier>,
+}
+
+impl PricingState {
+    pub const MAX_TIERS: usize = 16;
+    pub const MAX_SIZE: usize = 1 + 4 + Self::MAX_TIERS * std::mem::size_of::<PriceTier>();
+
+    /// tier a profile of `generation` falls into
+    pub fn tier(&self, generation: u64) -> PriceTier {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|tier| tier.from_generation <= generation)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn profile_minting_cost(&self, generation: u64) -> u64 {
+        self.tier(generation).profile_minting_cost
+    }
+
+    pub fn invitation_minting_cost(&self, generation: u64) -> u64 {
+        self.tier(generation).invitation_minting_cost
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone)]
+pub struct PricingInput {
+    pub tiers: Vec<PriceTier>,
+}
+
+impl PricingInput {
+    /// tiers have to start at generation 0 and be sorted by `from_generation`
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.tiers.is_empty() && self.tiers.len() <= PricingState::MAX_TIERS,
+            MyError::InvalidPriceTiers
+        );
+        require!(self.tiers[0].from_generation == 0, MyError::InvalidPriceTiers);
+        require!(
+            self.tiers
+                .windows(2)
+                .all(|pair| pair[0].from_generation < pair[1].from_generation),
+            MyError::InvalidPriceTiers
+        );
+        Ok(())
+    }
+
+    pub fn set_value(&self, state: &mut PricingState) {
+        state.tiers = self.tiers.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tier(from_generation: u64, cost: u64) -> PriceTier {
+        PriceTier {
+            from_generation,
+            profile_minting_cost: cost,
+            invitation_minting_cost: cost / 10,
+        }
+    }
+
+    #[test]
+    fn picks_the_last_tier_started() {
+        let state = PricingState {
+            _bump: 0,
+            tiers: vec![tier(0, 100), tier(3, 200), tier(10, 500)],
+        };
+        assert_eq!(state.profile_minting_cost(1), 100);
+        assert_eq!(state.profile_minting_cost(3), 200);
+        assert_eq!(state.profile_minting_cost(9), 200);
+        assert_eq!(state.invitation_minting_cost(42), 50);
+    }
+
+    #[test]
+    fn rejects_unsorted_or_offset_tiers() {
+        let input = |tiers| PricingInput { tiers };
+        assert!(input(vec![tier(0, 1), tier(2, 2)]).validate().is_ok());
+        assert!(input(vec![]).validate().is_err());
+        assert!(input(vec![tier(1, 1)]).validate().is_err());
+        assert!(input(vec![tier(0, 1), tier(2, 2), tier(2, 3)]).validate().is_err());
+    }
+}
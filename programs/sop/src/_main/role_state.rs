@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use anchor_lang::{AnchorDeserialize, AnchorSerialize};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// can call `create_collection`, `update_collection`, `set_metadata_constraints`
+    /// and `set_base_uris`
+    CollectionManager,
+    /// can call `update_main_state`, `update_pricing_state`, `set_lineage_config`,
+    /// `set_common_lut` and `set_invitation_quota`
+    FeeManager,
+    /// can pause and unpause the program
+    Pauser,
+    /// can call `revoke_activation_tokens` for any profile
+    CommunityManager,
+}
+
+impl Role {
+    pub fn seed(&self) -> &'static [u8] {
+        match self {
+            Role::CollectionManager => b"collection_manager",
+            Role::FeeManager => b"fee_manager",
+            Role::Pauser => b"pauser",
+            Role::CommunityManager => b"community_manager",
+        }
+    }
+}
+
+/// Exists for as long as `authority` holds `role`
+#[account]
+pub struct RoleState {
+    pub role: Role,
+    pub authority: Pubkey,
+    pub _bump: u8,
+}
+
+impl RoleState {
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MyError;
+
+/// Invitations minted by one `mint_activation_token` call, the `index`th issue of
+/// `activation_token`. Invitations are fungible once handed out, `mint_profile_by_at`
+/// redeems them against an issue that is neither expired, revoked nor used up.
+#[account]
+pub struct ActivationTokenIssueState {
+    pub activation_token: Pubkey,
+    pub index: u64,
+    /// invitations minted by this issue
+    pub amount: u64,
+    /// invitations of this issue used by `mint_profile_by_at`
+    pub redeemed: u64,
+    /// unix time after which the invitations can't be used, `0` for never
+    pub expires_at: i64,
+    /// set by `revoke_activation_tokens`
+    pub revoked: bool,
+    /// made by `migrate_activation_token_state` for the invitations minted before issues
+    pub legacy: bool,
+    pub _bump: u8,
+}
+
+impl ActivationTokenIssueState {
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+
+    /// uses one invitation of this issue
+    pub fn redeem(&mut self, now: i64) -> Result<()> {
+        require!(!self.revoked, MyError::ActivationTokensRevoked);
+        require!(!self.is_expired(now), MyError::ActivationTokensExpired);
+        require!(
+            self.redeemed < self.amount,
+            MyError::ActivationTokenIssueRedeemed
+        );
+        self.redeemed += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(amount: u64, expires_at: i64) -> ActivationTokenIssueState {
+        ActivationTokenIssueState {
+            activation_token: Pubkey::new_unique(),
+            index: 0,
+            amount,
+            redeemed: 0,
+            expires_at,
+            revoked: false,
+            legacy: false,
+            _bump: 0,
+        }
+    }
+
+    #[test]
+    fn expires_at_the_given_time() {
+        let mut issue = issue(1, 0);
+        assert!(!issue.is_expired(i64::MAX));
+
+        issue.expires_at = 100;
+        assert!(!issue.is_expired(99));
+        assert!(issue.is_expired(100));
+    }
+
+    #[test]
+    fn redeems_up_to_the_issued_amount() {
+        let mut live = issue(2, 100);
+        assert!(live.redeem(99).is_ok());
+        assert!(live.redeem(99).is_ok());
+        assert!(live.redeem(99).is_err());
+
+        assert!(issue(2, 100).redeem(100).is_err());
+
+        let mut revoked = issue(2, 0);
+        revoked.revoked = true;
+        assert!(revoked.redeem(0).is_err());
+    }
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::{activation_token::ActivationTokenIssueState, error::MyError};
+
+/// Invitations of one issue held by `holder`. Invitations only move with their receipt
+/// through `transfer_activation_tokens`, so `mint_profile_by_at` redeems a token against
+/// the issue it was minted by and not against any live issue of the same mint.
+#[account]
+pub struct ActivationTokenReceiptState {
+    pub issue: Pubkey,
+    pub holder: Pubkey,
+    /// invitations of `issue` `holder` can still redeem
+    pub amount: u64,
+    pub _bump: u8,
+}
+
+impl ActivationTokenReceiptState {
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
+
+    /// uses one invitation of `issue`, the issue this receipt was made for
+    pub fn redeem(
+        &mut self,
+        issue_key: &Pubkey,
+        issue: &mut ActivationTokenIssueState,
+        now: i64,
+    ) -> Result<()> {
+        require_keys_eq!(self.issue, *issue_key, MyError::InvalidActivationTokenIssue);
+        require!(self.amount > 0, MyError::NotEnoughActivationTokens);
+        issue.redeem(now)?;
+        self.amount -= 1;
+        Ok(())
+    }
+
+    pub fn take(&mut self, amount: u64) -> Result<()> {
+        self.amount = self
+            .amount
+            .checked_sub(amount)
+            .ok_or(MyError::NotEnoughActivationTokens)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(revoked: bool) -> ActivationTokenIssueState {
+        ActivationTokenIssueState {
+            activation_token: Pubkey::default(),
+            index: 0,
+            amount: 10,
+            redeemed: 0,
+            expires_at: 0,
+            revoked,
+            legacy: false,
+            _bump: 0,
+        }
+    }
+
+    #[test]
+    fn redeems_against_its_own_issue_only() {
+        let (revoked_key, live_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut revoked, mut live) = (issue(true), issue(false));
+        let mut receipt = ActivationTokenReceiptState {
+            issue: revoked_key,
+            holder: Pubkey::new_unique(),
+            amount: 1,
+            _bump: 0,
+        };
+
+        // a token of the revoked issue can't use up the live one
+        assert!(receipt.redeem(&live_key, &mut live, 0).is_err());
+        assert_eq!(live.redeemed, 0);
+        assert!(receipt.redeem(&revoked_key, &mut revoked, 0).is_err());
+
+        receipt.issue = live_key;
+        assert!(receipt.redeem(&live_key, &mut live, 0).is_ok());
+        assert_eq!((receipt.amount, live.redeemed), (0, 1));
+        assert!(receipt.redeem(&live_key, &mut live, 0).is_err());
+    }
+}
@@ -6,8 +6,15 @@ pub struct ActivationTokenState {
     // lineage: LineageInfo,
     pub parent_profile: Pubkey,
     pub creator: Pubkey,
+    /// layout version, bumped by `migrate_activation_token_state`
+    pub version: u8,
+    /// set by the profile holder with `set_invitation_price`, `0` uses the `PricingState` cost
+    pub invitation_price: u64,
+    /// issues made by `mint_activation_token`, the next one gets this index
+    pub total_issues: u64,
 }
 
 impl ActivationTokenState {
     pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
+    pub const VERSION: u8 = 3;
 }
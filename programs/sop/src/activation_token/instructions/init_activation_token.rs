@@ -15,7 +15,9 @@ use solana_program::program::{invoke, invoke_signed};
 use crate::{
     _main::MainState,
     activation_token::ActivationTokenState,
-    constants::{SEED_ACTIVATION_TOKEN_STATE, SEED_MAIN_STATE, SEED_PROFILE_STATE},
+    constants::{
+        PAUSE_ACTIVATION_TOKENS, SEED_ACTIVATION_TOKEN_STATE, SEED_MAIN_STATE, SEED_PROFILE_STATE,
+    },
     error::MyError,
     other_states::LineageInfo,
     profile::profile_state::ProfileState,
@@ -26,8 +28,9 @@ pub fn init_activation_token(
     ctx: Context<AInitActivationToken>,
     name: String,
     symbol: String,
-    uri: String,
+    uri_hash: String,
 ) -> Result<()> {
+    let uri = ctx.accounts.main_state.activation_token_uri(&uri_hash)?;
     {
         //NOTE: setup and validation
         let main_state = &mut ctx.accounts.main_state;
@@ -38,6 +41,7 @@ pub fn init_activation_token(
         profile_state.activation_token = Some(ctx.accounts.activation_token.key());
         activation_token_state.parent_profile = ctx.accounts.profile.key();
         activation_token_state.creator = ctx.accounts.user.key();
+        activation_token_state.version = ActivationTokenState::VERSION;
         //TODO: update some main state if fiels are avaible (may be in future)
     }
     {
@@ -71,6 +75,7 @@ pub struct AInitActivationToken<'info> {
         mut,
         seeds = [SEED_MAIN_STATE],
         bump,
+        constraint = !main_state.is_paused(PAUSE_ACTIVATION_TOKENS) @ MyError::ActivationTokensPaused,
     )]
     pub main_state: Box<Account<'info, MainState>>,
 
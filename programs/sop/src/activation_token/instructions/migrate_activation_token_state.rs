@@ -0,0 +1,138 @@
+use crate::{
+    _main::main_state::MainState,
+    activation_token::{
+        ActivationTokenIssueState, ActivationTokenReceiptState, ActivationTokenState,
+    },
+    constants::{
+        SEED_ACTIVATION_TOKEN_ISSUE, SEED_ACTIVATION_TOKEN_RECEIPT, SEED_ACTIVATION_TOKEN_STATE,
+        SEED_MAIN_STATE,
+    },
+    error::MyError,
+    utils::migrate_account,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+/// Grows an existing `ActivationTokenState` to the current layout and stamps
+/// `ActivationTokenState::VERSION`.
+/// `legacy_issue` makes the invitations minted before issues existed redeemable, as the
+/// first issue covering the current supply without expiry. Their holders then get a receipt
+/// of it through `import_legacy_activation_tokens`.
+pub fn migrate_activation_token_state(ctx: Context<AMigrateActivationTokenState>) -> Result<()> {
+    let legacy_issue = ctx.accounts.legacy_issue.is_some();
+    migrate_account::<ActivationTokenState>(
+        ctx.accounts.activation_token_state.to_account_info(),
+        ctx.accounts.owner.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        8 + ActivationTokenState::MAX_SIZE,
+        |state| {
+            if legacy_issue {
+                state.total_issues += 1;
+            }
+            state.version = ActivationTokenState::VERSION;
+        },
+    )?;
+
+    if let Some(issue) = &mut ctx.accounts.legacy_issue {
+        issue.activation_token = ctx.accounts.activation_token.key();
+        issue.index = 0;
+        issue.amount = ctx.accounts.activation_token.supply;
+        issue.legacy = true;
+        issue._bump = *ctx.bumps.get("legacy_issue").unwrap();
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AMigrateActivationTokenState<'info> {
+    #[account(mut, address = main_state.owner @ MyError::OnlyOwnerCanCall)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_MAIN_STATE],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    pub activation_token: Box<Account<'info, Mint>>,
+
+    ///CHECK: deserialized in `migrate_activation_token_state` once it has the current size
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [SEED_ACTIVATION_TOKEN_STATE, activation_token.key().as_ref()],
+        bump,
+    )]
+    pub activation_token_state: AccountInfo<'info>,
+
+    // only once, before `mint_activation_token` made any issue
+    #[account(
+        init,
+        payer = owner,
+        seeds = [SEED_ACTIVATION_TOKEN_ISSUE, activation_token.key().as_ref(), &0u64.to_le_bytes()],
+        bump,
+        space = 8 + ActivationTokenIssueState::MAX_SIZE,
+    )]
+    pub legacy_issue: Option<Box<Account<'info, ActivationTokenIssueState>>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Gives the holder of `holder_ata` a receipt of the legacy issue for its current balance.
+/// Only until the first new issue, after which balances also hold invitations of other issues.
+pub fn import_legacy_activation_tokens(ctx: Context<AImportLegacyActivationTokens>) -> Result<()> {
+    let receipt = &mut ctx.accounts.receipt;
+    receipt.issue = ctx.accounts.legacy_issue.key();
+    receipt.holder = ctx.accounts.holder_ata.owner;
+    receipt.amount = ctx.accounts.holder_ata.amount;
+    receipt._bump = *ctx.bumps.get("receipt").unwrap();
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AImportLegacyActivationTokens<'info> {
+    #[account(mut, address = main_state.owner @ MyError::OnlyOwnerCanCall)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_MAIN_STATE],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    ///CHECK: only used as a seed
+    pub activation_token: AccountInfo<'info>,
+
+    #[account(
+        seeds = [SEED_ACTIVATION_TOKEN_STATE, activation_token.key().as_ref()],
+        bump,
+        constraint = activation_token_state.total_issues == 1 @ MyError::LegacyActivationTokensClosed,
+    )]
+    pub activation_token_state: Box<Account<'info, ActivationTokenState>>,
+
+    #[account(
+        seeds = [SEED_ACTIVATION_TOKEN_ISSUE, activation_token.key().as_ref(), &0u64.to_le_bytes()],
+        bump = legacy_issue._bump,
+        constraint = legacy_issue.legacy @ MyError::LegacyActivationTokensClosed,
+    )]
+    pub legacy_issue: Box<Account<'info, ActivationTokenIssueState>>,
+
+    #[account(token::mint = activation_token)]
+    pub holder_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [
+            SEED_ACTIVATION_TOKEN_RECEIPT,
+            legacy_issue.key().as_ref(),
+            holder_ata.owner.as_ref(),
+        ],
+        bump,
+        space = 8 + ActivationTokenReceiptState::MAX_SIZE,
+    )]
+    pub receipt: Box<Account<'info, ActivationTokenReceiptState>>,
+
+    pub system_program: Program<'info, System>,
+}
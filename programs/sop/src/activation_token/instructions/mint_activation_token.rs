@@ -11,27 +11,87 @@ use mpl_token_metadata::{
 use solana_program::program::{invoke, invoke_signed};
 
 use crate::{
-    _main::MainState,
-    activation_token::ActivationTokenState,
-    constants::{SEED_ACTIVATION_TOKEN_STATE, SEED_MAIN_STATE, SEED_PROFILE_STATE, TOTAL_SELLER_BASIS_POINTS},
+    _main::{MainState, PaymentMint, PaymentState, PricingState},
+    activation_token::{
+        ActivationTokenIssueState, ActivationTokenReceiptState, ActivationTokenState,
+    },
+    constants::{
+        PAUSE_ACTIVATION_TOKENS, SEED_ACTIVATION_TOKEN_ISSUE, SEED_ACTIVATION_TOKEN_RECEIPT,
+        SEED_ACTIVATION_TOKEN_STATE, SEED_GENERATION_STATE, SEED_MAIN_STATE, SEED_PAYMENT_STATE,
+        SEED_PRICING_STATE, SEED_PROFILE_STATE, TOTAL_SELLER_BASIS_POINTS,
+    },
+    curve::{CurveV0, TokenBondingV0},
     error::MyError,
     other_states::LineageInfo,
-    profile::profile_state::ProfileState,
-    utils::{_verify_collection,init_ata_if_needed, transfer_tokens},
+    profile::{profile_state::ProfileState, record_earnings, EarningsSource, GenerationState},
+    utils::{
+        _verify_collection, init_ata_if_needed, lineage_holder, transfer_sol, transfer_tokens,
+    },
 };
 
-pub fn mint_activation_token(ctx: Context<AMintActivationToken>, amount: u64) -> Result<()> {
-    let minter = ctx.accounts.minter.to_account_info();
-    let mint = ctx.accounts.activation_token.to_account_info();
+/// Accounts of each lineage level in the `remaining_accounts` of `mint_activation_token`
+pub const INVITATION_LEVEL_ACCOUNTS: usize = 4;
+
+/// `remaining_accounts` holds for each of the first `main_state.lineage_depth` ancestors of
+/// the invitees, the inviting profile first, and then genesis:
+/// - the profile state, its `current_holder` gets paid. The share of a retired ancestor,
+///   whose state is closed, goes to genesis
+/// - the holder's wallet, paid directly when paying in native SOL
+/// - the holder's ata of the mint paid with, created by `pay` if needed
+/// - the earnings account of the profile, see `record_earnings`
+///
+/// The price is paid in OPOS, or in `payment_mint` when one is passed, and goes to the
+/// lineage holders in the mint paid with. `max_payment` caps the amount of `payment_mint`
+/// charged, it is required for curve priced mints. The lifetime earnings only count OPOS.
+/// Every call is a new issue, its invitations expire at `expires_at` (unix time) when one
+/// is given, and the receiver gets a receipt of it, see `ActivationTokenReceiptState`.
+/// Minting is limited by `main_state.invitation_quota`.
+pub fn mint_activation_token<'info>(
+    ctx: Context<'_, '_, '_, 'info, AMintActivationToken<'info>>,
+    amount: u64,
+    expires_at: Option<i64>,
+    max_payment: Option<u64>,
+) -> Result<()> {
+    let expires_at = expires_at.unwrap_or_default();
+    require!(
+        expires_at == 0 || expires_at > Clock::get()?.unix_timestamp,
+        MyError::InvalidExpiry
+    );
     let activation_token_state = &mut ctx.accounts.activation_token_state;
-    let main_state = &mut ctx.accounts.main_state;
+    let issue = &mut ctx.accounts.activation_token_issue;
+    issue.activation_token = ctx.accounts.activation_token.key();
+    issue.index = activation_token_state.total_issues;
+    issue.amount = amount;
+    issue.expires_at = expires_at;
+    issue._bump = *ctx.bumps.get("activation_token_issue").unwrap();
+    activation_token_state.total_issues += 1;
+    let receipt = &mut ctx.accounts.activation_token_receipt;
+    receipt.issue = issue.key();
+    receipt.holder = ctx.accounts.receiver_ata.owner;
+    receipt.amount = amount;
+    receipt._bump = *ctx.bumps.get("activation_token_receipt").unwrap();
+
+    let mint = ctx.accounts.activation_token.to_account_info();
+    let receiver_ata = ctx.accounts.receiver_ata.to_account_info();
+    let main_state = &ctx.accounts.main_state;
     let token_program = ctx.accounts.token_program.to_account_info();
     let profile_state = &mut ctx.accounts.profile_state;
+    let generation_state = &mut ctx.accounts.generation_state;
+    generation_state.generation = profile_state.lineage.generation;
+    generation_state._bump = *ctx.bumps.get("generation_state").unwrap();
+    main_state.invitation_quota.consume(
+        amount,
+        profile_state.total_minted_sft,
+        generation_state.total_invitations,
+        &mut profile_state.invitation_window,
+        Clock::get()?.unix_timestamp,
+    )?;
     profile_state.total_minted_sft += amount;
+    generation_state.total_invitations += amount;
 
     let cpi_accounts = MintTo {
         mint,
-        to: ctx.accounts.receiver_ata.to_account_info(),
+        to: receiver_ata,
         authority: main_state.to_account_info(),
     };
 
@@ -45,23 +105,69 @@ pub fn mint_activation_token(ctx: Context<AMintActivationToken>, amount: u64) ->
     )?;
 
     // NOTE: minting cost distribution
-    let token_program = ctx.accounts.token_program.to_account_info();
-    let sender_ata = ctx.accounts.user_opos_ata.to_account_info();
-    let authority = ctx.accounts.minter.to_account_info();
-    let main_state = &mut ctx.accounts.main_state;
-    let cost = main_state.invitation_minting_cost * amount;
-
-    // Genesis
-    transfer_tokens(
-        sender_ata.to_account_info(),
-        ctx.accounts
-            .genesis_profile_holder_opos_ata
-            .to_account_info(),
-        authority.to_account_info(),
-        token_program.to_account_info(),
-        cost as u64,
-    )?;
-    
+    let cost = ctx
+        .accounts
+        .invitation_price()
+        .checked_mul(amount)
+        .ok_or(MyError::MathOverflow)?;
+    let (payment, cost) = ctx.accounts.charge(cost, max_payment)?;
+    let main_state = &ctx.accounts.main_state;
+    let accounts = &ctx.accounts;
+    // invitees join below the inviting profile, their lineage starts with it
+    let ancestors = accounts
+        .parent_profile_state
+        .child_ancestors(main_state.genesis_profile);
+    let mut lineage = main_state.lineage_split(&ancestors, cost)?;
+    require!(
+        ctx.remaining_accounts.len() == lineage.len() * INVITATION_LEVEL_ACCOUNTS,
+        MyError::InvalidLineageAccounts
+    );
+
+    // the share of a retired ancestor goes to genesis, like its minting cost
+    let genesis_level = lineage.len() - 1;
+    let mut holders = Vec::with_capacity(lineage.len());
+    for (level, level_accounts) in ctx
+        .remaining_accounts
+        .chunks(INVITATION_LEVEL_ACCOUNTS)
+        .enumerate()
+    {
+        let holder = lineage_holder(&level_accounts[0], &lineage[level].0)?;
+        if holder.is_none() && level != genesis_level {
+            lineage[genesis_level].1 += lineage[level].1;
+            lineage[level].1 = 0;
+        }
+        holders.push(holder);
+    }
+
+    // the inviting profile pays for its own invitations today so its share only moves
+    // when the holder differs from the minter
+    let mut earnings = Vec::with_capacity(lineage.len());
+    let mut earnings_accounts = Vec::with_capacity(lineage.len());
+    for (((profile, value), level_accounts), holder) in lineage
+        .into_iter()
+        .zip(ctx.remaining_accounts.chunks(INVITATION_LEVEL_ACCOUNTS))
+        .zip(holders)
+    {
+        if value == 0 {
+            continue;
+        }
+        let (holder_account, holder_ata, profile_earnings) =
+            (&level_accounts[1], &level_accounts[2], &level_accounts[3]);
+        let holder = holder.ok_or(MyError::InvalidLineageHolder)?;
+        require_keys_eq!(holder_account.key(), holder, MyError::InvalidLineageHolder);
+        if holder == accounts.minter.key() {
+            continue;
+        }
+        accounts.pay(payment, holder_account, holder_ata, value)?;
+        earnings.push((profile, EarningsSource::Invitation, value));
+        earnings_accounts.push(profile_earnings.clone());
+    }
+
+    // lifetime earnings are kept in OPOS, and only count what was actually paid
+    if payment.is_none() {
+        record_earnings(&earnings_accounts, &earnings)?;
+    }
+
     Ok(())
 }
 
@@ -69,7 +175,7 @@ pub fn mint_activation_token(ctx: Context<AMintActivationToken>, amount: u64) ->
 pub struct AMintActivationToken<'info> {
     #[account(
         mut,
-        address = activation_token_state.creator
+        address = profile_state.current_holder @ MyError::OnlyProfileHolderAllow,
     )]
     pub minter: Signer<'info>,
 
@@ -92,9 +198,16 @@ pub struct AMintActivationToken<'info> {
         mut,
         seeds = [SEED_MAIN_STATE],
         bump,
+        constraint = !main_state.is_paused(PAUSE_ACTIVATION_TOKENS) @ MyError::ActivationTokensPaused,
     )]
     pub main_state: Box<Account<'info, MainState>>,
 
+    #[account(
+        seeds = [SEED_PRICING_STATE],
+        bump = pricing_state._bump,
+    )]
+    pub pricing_state: Box<Account<'info, PricingState>>,
+
     #[account(
         mut,
         address = profile_state.activation_token.unwrap() @ MyError::ActivationTokenNotFound
@@ -108,6 +221,32 @@ pub struct AMintActivationToken<'info> {
     )]
     pub activation_token_state: Box<Account<'info, ActivationTokenState>>,
 
+    #[account(
+        init,
+        payer = minter,
+        seeds = [
+            SEED_ACTIVATION_TOKEN_ISSUE,
+            activation_token.key().as_ref(),
+            &activation_token_state.total_issues.to_le_bytes(),
+        ],
+        bump,
+        space = 8 + ActivationTokenIssueState::MAX_SIZE,
+    )]
+    pub activation_token_issue: Box<Account<'info, ActivationTokenIssueState>>,
+
+    #[account(
+        init,
+        payer = minter,
+        seeds = [
+            SEED_ACTIVATION_TOKEN_RECEIPT,
+            activation_token_issue.key().as_ref(),
+            receiver_ata.owner.as_ref(),
+        ],
+        bump,
+        space = 8 + ActivationTokenReceiptState::MAX_SIZE,
+    )]
+    pub activation_token_receipt: Box<Account<'info, ActivationTokenReceiptState>>,
+
     #[account()]
     pub profile: Box<Account<'info, Mint>>,
 
@@ -117,17 +256,22 @@ pub struct AMintActivationToken<'info> {
         bump,
     )]
     pub profile_state: Box<Account<'info, ProfileState>>,
+
+    #[account(
+        init_if_needed,
+        payer = minter,
+        seeds = [SEED_GENERATION_STATE, &profile_state.lineage.generation.to_le_bytes()],
+        bump,
+        space = 8 + GenerationState::MAX_SIZE,
+    )]
+    pub generation_state: Box<Account<'info, GenerationState>>,
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 
     //NOTE: profile minting cost distribution account
-    // #[account(address = activation_token_state.parent_profile @ MyError::ProfileIdMissMatch)]
+    #[account(address = activation_token_state.parent_profile @ MyError::ProfileIdMissMatch)]
     pub parent_profile: Box<Account<'info, Mint>>,
-    pub grand_parent_profile: Box<Account<'info, Mint>>,
-    pub great_grand_parent_profile: Box<Account<'info, Mint>>,
-    pub ggreate_grand_parent_profile: Box<Account<'info, Mint>>,
-    pub genesis_profile: Box<Account<'info, Mint>>,
 
     #[account(
         mut,
@@ -140,137 +284,123 @@ pub struct AMintActivationToken<'info> {
     #[account(address = main_state.opos_token)]
     pub opos_token: AccountInfo<'info>,
 
-    // Current parent profile holded ata
-    #[account(
-        token::mint = parent_profile_state.mint,
-        constraint = current_parent_profile_holder_ata.amount == 1
-    )]
-    pub current_parent_profile_holder_ata: Box<Account<'info, TokenAccount>>,
-    #[account(
-        token::mint = parent_profile_state.lineage.parent,
-        constraint = current_grand_parent_profile_holder_ata.amount == 1
-    )]
-    pub current_grand_parent_profile_holder_ata: Box<Account<'info, TokenAccount>>,
-    #[account(
-        token::mint = parent_profile_state.lineage.grand_parent,
-        constraint = current_great_grand_parent_profile_holder_ata.amount == 1
-    )]
-    pub current_great_grand_parent_profile_holder_ata: Box<Account<'info, TokenAccount>>,
-    #[account(
-        token::mint = parent_profile_state.lineage.great_grand_parent,
-        constraint = current_ggreat_grand_parent_profile_holder_ata.amount == 1
-    )]
-    pub current_ggreat_grand_parent_profile_holder_ata: Box<Account<'info, TokenAccount>>,
-    #[account(
-        token::mint = main_state.genesis_profile,
-        constraint = current_genesis_profile_holder_ata.amount == 1
-    )]
-    pub current_genesis_profile_holder_ata: Box<Account<'info, TokenAccount>>,
-
-    // Current profile holders
-    ///CHECK:
-    #[account(address = current_parent_profile_holder_ata.owner)]
-    pub current_parent_profile_holder: AccountInfo<'info>,
-    ///CHECK:
-    #[account(address = current_grand_parent_profile_holder_ata.owner)]
-    pub current_grand_parent_profile_holder: AccountInfo<'info>,
-    ///CHECK:
-    #[account(address = current_great_grand_parent_profile_holder_ata.owner)]
-    pub current_great_grand_parent_profile_holder: AccountInfo<'info>,
-    ///CHECK:
-    #[account(address = current_ggreat_grand_parent_profile_holder_ata.owner)]
-    pub current_ggreat_grand_parent_profile_holder: AccountInfo<'info>,
-    ///CHECK:
-    #[account(address = current_genesis_profile_holder_ata.owner)]
-    pub current_genesis_profile_holder: AccountInfo<'info>,
-
     // Current Profile holder's opos token ata
     #[account(
         mut,
         token::mint = opos_token,
         token::authority = minter,
-        constraint= user_opos_ata.amount >= main_state.invitation_minting_cost @ MyError::NotEnoughTokenToMint
-    )]
-    pub user_opos_ata: Box<Account<'info, TokenAccount>>,
-    ///CHECK:
-    #[account(
-        mut,
-        constraint = init_ata_if_needed(
-            opos_token.to_account_info(),
-            parent_profile_holder_opos_ata.to_account_info(),
-            current_parent_profile_holder.to_account_info(),
-            minter.to_account_info(),
-            token_program.to_account_info(),
-            system_program.to_account_info(),
-            associated_token_program.to_account_info(),
-        ) == Ok(())
-        // token::mint = opos_token,
-        // token::authority = current_parent_profile_holder,
-    )]
-    // pub parent_profile_holder_opos_ata: Box<Account<'info, TokenAccount>>,
-    pub parent_profile_holder_opos_ata: AccountInfo<'info>,
-    ///CHECK:
-    #[account(
-        mut,
-        constraint = init_ata_if_needed(
-            opos_token.to_account_info(),
-            grand_parent_profile_holder_opos_ata.to_account_info(),
-            current_grand_parent_profile_holder.to_account_info(),
-            minter.to_account_info(),
-            token_program.to_account_info(),
-            system_program.to_account_info(),
-            associated_token_program.to_account_info(),
-        ) == Ok(())
-        // token::mint = opos_token,
-        // token::authority = current_grand_parent_profile_holder,
     )]
-    pub grand_parent_profile_holder_opos_ata: AccountInfo<'info>,
-    ///CHECK:
+    pub user_opos_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Paying in another mint than OPOS, the lineage holder atas are then atas of `payment_mint`
     #[account(
-        mut,
-        constraint = init_ata_if_needed(
-            opos_token.to_account_info(),
-            great_grand_parent_profile_holder_opos_ata.to_account_info(),
-            current_great_grand_parent_profile_holder.to_account_info(),
-            minter.to_account_info(),
-            token_program.to_account_info(),
-            system_program.to_account_info(),
-            associated_token_program.to_account_info(),
-        ) == Ok(())
-        // token::mint = opos_token,
-        // token::authority = current_great_grand_parent_profile_holder,
+        seeds = [SEED_PAYMENT_STATE],
+        bump = payment_state._bump,
     )]
-    pub great_grand_parent_profile_holder_opos_ata: AccountInfo<'info>,
-    ///CHECK:
+    pub payment_state: Option<Box<Account<'info, PaymentState>>>,
+
+    pub payment_mint: Option<Box<Account<'info, Mint>>>,
+
     #[account(
         mut,
-        constraint = init_ata_if_needed(
-            opos_token.to_account_info(),
-            ggreat_grand_parent_profile_holder_opos_ata.to_account_info(),
-            current_ggreat_grand_parent_profile_holder.to_account_info(),
-            minter.to_account_info(),
-            token_program.to_account_info(),
-            system_program.to_account_info(),
-            associated_token_program.to_account_info(),
-        ) == Ok(())
-        // token::mint = opos_token,
-        // token::authority = current_ggreat_grand_parent_profile_holder,
+        token::mint = payment_mint,
+        token::authority = minter,
     )]
-    pub ggreat_grand_parent_profile_holder_opos_ata: AccountInfo<'info>,
-    ///CHECK:
-    #[account(
-        mut,
-        constraint = init_ata_if_needed(
-            opos_token.to_account_info(),
-            genesis_profile_holder_opos_ata.to_account_info(),
-            current_genesis_profile_holder.to_account_info(),
+    pub user_payment_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Pricing of curve priced payment mints
+    pub token_bonding: Option<Box<Account<'info, TokenBondingV0>>>,
+    pub curve: Option<Box<Account<'info, CurveV0>>>,
+    pub bonding_base_storage: Option<Box<Account<'info, TokenAccount>>>,
+}
+
+impl<'info> AMintActivationToken<'info> {
+    /// price of a single invitation
+    pub fn invitation_price(&self) -> u64 {
+        // invitees join one generation below the inviting profile
+        let default_price = self
+            .pricing_state
+            .invitation_minting_cost(self.profile_state.lineage.generation + 1);
+        self.main_state
+            .invitation_price(self.activation_token_state.invitation_price, default_price)
+    }
+
+    /// the payment mint used and the amount charged for `cost`, `None` when paying in OPOS
+    pub fn charge(
+        &self,
+        cost: u64,
+        max_payment: Option<u64>,
+    ) -> Result<(Option<PaymentMint>, u64)> {
+        let payment_mint = match &self.payment_mint {
+            Some(payment_mint) => payment_mint,
+            None => {
+                let user_opos_ata = self
+                    .user_opos_ata
+                    .as_ref()
+                    .ok_or(MyError::InvalidPaymentAccounts)?;
+                require!(user_opos_ata.amount >= cost, MyError::NotEnoughTokenToMint);
+                return Ok((None, cost));
+            }
+        };
+        let payment_state = self
+            .payment_state
+            .as_ref()
+            .ok_or(MyError::InvalidPaymentAccounts)?;
+        let (_, payment, cost) = payment_state.charge(
+            payment_mint,
+            &Account::<Mint>::try_from(&self.opos_token)?,
+            cost,
+            max_payment,
+            self.token_bonding.as_deref(),
+            self.curve.as_deref(),
+            self.bonding_base_storage.as_deref(),
+        )?;
+        Ok((Some(payment), cost))
+    }
+
+    /// pays `holder` its share in the mint paid with, into `holder_ata` unless it's native SOL
+    pub fn pay(
+        &self,
+        payment: Option<PaymentMint>,
+        holder: &AccountInfo<'info>,
+        holder_ata: &AccountInfo<'info>,
+        value: u64,
+    ) -> Result<()> {
+        let minter = self.minter.to_account_info();
+        let (mint, sender_ata) = match payment {
+            Some(payment) if payment.is_native() => {
+                return transfer_sol(
+                    minter,
+                    holder.to_account_info(),
+                    self.system_program.to_account_info(),
+                    value,
+                );
+            }
+            Some(_) => (
+                self.payment_mint.as_ref().map(|mint| mint.to_account_info()),
+                &self.user_payment_ata,
+            ),
+            None => (Some(self.opos_token.to_account_info()), &self.user_opos_ata),
+        };
+        let (mint, sender_ata) = match (mint, sender_ata) {
+            (Some(mint), Some(sender_ata)) => (mint, sender_ata),
+            _ => return err!(MyError::InvalidPaymentAccounts),
+        };
+        init_ata_if_needed(
+            mint,
+            holder_ata.to_account_info(),
+            holder.to_account_info(),
             minter.to_account_info(),
-            token_program.to_account_info(),
-            system_program.to_account_info(),
-            associated_token_program.to_account_info(),
-        ) == Ok(())
-        // token::mint = opos_token,
-        // token::authority = current_genesis_profile_holder,
-    )]
-    pub genesis_profile_holder_opos_ata: AccountInfo<'info>,
+            self.token_program.to_account_info(),
+            self.system_program.to_account_info(),
+            self.associated_token_program.to_account_info(),
+        )?;
+        transfer_tokens(
+            sender_ata.to_account_info(),
+            holder_ata.to_account_info(),
+            minter,
+            self.token_program.to_account_info(),
+            value,
+        )
+    }
 }
@@ -3,3 +3,15 @@ pub use init_activation_token::*;
 
 pub mod mint_activation_token;
 pub use mint_activation_token::*;
+
+pub mod migrate_activation_token_state;
+pub use migrate_activation_token_state::*;
+
+pub mod set_invitation_price;
+pub use set_invitation_price::*;
+
+pub mod revoke_activation_tokens;
+pub use revoke_activation_tokens::*;
+
+pub mod transfer_activation_tokens;
+pub use transfer_activation_tokens::*;
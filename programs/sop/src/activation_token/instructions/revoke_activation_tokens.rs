@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    _main::{
+        role_state::{Role, RoleState},
+        MainState,
+    },
+    activation_token::{ActivationTokenIssueState, ActivationTokenState},
+    constants::{
+        SEED_ACTIVATION_TOKEN_STATE, SEED_MAIN_STATE, SEED_PROFILE_STATE, SEED_ROLE_STATE,
+    },
+    error::MyError,
+    profile::ProfileState,
+};
+
+/// Revokes the issues passed as `remaining_accounts`, `mint_profile_by_at` no longer
+/// redeems invitations against them.
+pub fn revoke_activation_tokens<'info>(
+    ctx: Context<'_, '_, '_, 'info, ARevokeActivationTokens<'info>>,
+) -> Result<()> {
+    let activation_token = ctx.accounts.activation_token.key();
+    for info in ctx.remaining_accounts {
+        require!(info.is_writable, MyError::InvalidActivationTokenIssue);
+        let mut issue = Account::<ActivationTokenIssueState>::try_from(info)?;
+        require_keys_eq!(
+            issue.activation_token,
+            activation_token,
+            MyError::InvalidActivationTokenIssue
+        );
+        issue.revoked = true;
+        issue.exit(&crate::ID)?;
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ARevokeActivationTokens<'info> {
+    #[account(
+        constraint = authority.key() == profile_state.current_holder
+            || main_state.is_authorized(
+                &authority.key(),
+                &role_state,
+                Role::CommunityManager,
+            ) @ MyError::MissingRole,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_MAIN_STATE],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    ///CHECK: only used as the activation token state seed
+    pub activation_token: AccountInfo<'info>,
+
+    #[account(
+        seeds = [SEED_ACTIVATION_TOKEN_STATE, activation_token.key().as_ref()],
+        bump,
+    )]
+    pub activation_token_state: Box<Account<'info, ActivationTokenState>>,
+
+    #[account(
+        seeds = [SEED_PROFILE_STATE, activation_token_state.parent_profile.as_ref()],
+        bump,
+    )]
+    pub profile_state: Box<Account<'info, ProfileState>>,
+
+    #[account(
+        seeds = [SEED_ROLE_STATE, Role::CommunityManager.seed(), authority.key().as_ref()],
+        bump = role_state._bump,
+    )]
+    pub role_state: Option<Box<Account<'info, RoleState>>>,
+}
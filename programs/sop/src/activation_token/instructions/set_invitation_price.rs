@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+use crate::{
+    _main::MainState,
+    activation_token::ActivationTokenState,
+    constants::{SEED_ACTIVATION_TOKEN_STATE, SEED_MAIN_STATE, SEED_PROFILE_STATE},
+    error::MyError,
+    profile::profile_state::ProfileState,
+};
+
+/// Sets the price of the invitations issued by `profile`, `0` goes back to the default price
+pub fn set_invitation_price(ctx: Context<ASetInvitationPrice>, price: u64) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    require!(
+        price == 0
+            || (main_state.min_invitation_price..=main_state.max_invitation_price)
+                .contains(&price),
+        MyError::InvitationPriceOutOfBounds
+    );
+
+    let activation_token_state = &mut ctx.accounts.activation_token_state;
+    activation_token_state.invitation_price = price;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ASetInvitationPrice<'info> {
+    pub holder: Signer<'info>,
+
+    #[account(
+        token::mint = profile,
+        token::authority = holder,
+        constraint = holder_profile_ata.amount == 1 @ MyError::OnlyProfileHolderAllow,
+    )]
+    pub holder_profile_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [SEED_MAIN_STATE],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account()]
+    pub profile: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [SEED_PROFILE_STATE, profile.key().as_ref()],
+        bump,
+    )]
+    pub profile_state: Box<Account<'info, ProfileState>>,
+
+    ///CHECK:
+    #[account(address = profile_state.activation_token.unwrap() @ MyError::ActivationTokenNotFound)]
+    pub activation_token: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_ACTIVATION_TOKEN_STATE, activation_token.key().as_ref()],
+        bump,
+        constraint = activation_token_state.parent_profile == profile.key() @ MyError::ProfileIdMissMatch,
+    )]
+    pub activation_token_state: Box<Account<'info, ActivationTokenState>>,
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{
+    _main::MainState,
+    activation_token::{ActivationTokenIssueState, ActivationTokenReceiptState},
+    constants::{
+        PAUSE_ACTIVATION_TOKENS, SEED_ACTIVATION_TOKEN_ISSUE, SEED_ACTIVATION_TOKEN_RECEIPT,
+        SEED_MAIN_STATE,
+    },
+    error::MyError,
+    utils::transfer_tokens,
+};
+
+/// Hands `amount` invitations of `activation_token_issue` to the owner of `receiver_ata`,
+/// moving their receipt along so the receiver can redeem them
+pub fn transfer_activation_tokens(
+    ctx: Context<ATransferActivationTokens>,
+    amount: u64,
+) -> Result<()> {
+    ctx.accounts.holder_receipt.take(amount)?;
+
+    let receiver_receipt = &mut ctx.accounts.receiver_receipt;
+    receiver_receipt.issue = ctx.accounts.activation_token_issue.key();
+    receiver_receipt.holder = ctx.accounts.receiver_ata.owner;
+    receiver_receipt.amount += amount;
+    receiver_receipt._bump = *ctx.bumps.get("receiver_receipt").unwrap();
+
+    transfer_tokens(
+        ctx.accounts.holder_ata.to_account_info(),
+        ctx.accounts.receiver_ata.to_account_info(),
+        ctx.accounts.holder.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        amount,
+    )
+}
+
+#[derive(Accounts)]
+pub struct ATransferActivationTokens<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_MAIN_STATE],
+        bump,
+        constraint = !main_state.is_paused(PAUSE_ACTIVATION_TOKENS) @ MyError::ActivationTokensPaused,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    pub activation_token: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [
+            SEED_ACTIVATION_TOKEN_ISSUE,
+            activation_token.key().as_ref(),
+            &activation_token_issue.index.to_le_bytes(),
+        ],
+        bump = activation_token_issue._bump,
+    )]
+    pub activation_token_issue: Box<Account<'info, ActivationTokenIssueState>>,
+
+    #[account(
+        mut,
+        token::mint = activation_token,
+        token::authority = holder,
+    )]
+    pub holder_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = activation_token,
+        // both receipts would be the same account
+        constraint = receiver_ata.owner != holder.key() @ MyError::ActivationTokenSelfTransfer,
+    )]
+    pub receiver_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_ACTIVATION_TOKEN_RECEIPT,
+            activation_token_issue.key().as_ref(),
+            holder.key().as_ref(),
+        ],
+        bump = holder_receipt._bump,
+    )]
+    pub holder_receipt: Box<Account<'info, ActivationTokenReceiptState>>,
+
+    #[account(
+        init_if_needed,
+        payer = holder,
+        seeds = [
+            SEED_ACTIVATION_TOKEN_RECEIPT,
+            activation_token_issue.key().as_ref(),
+            receiver_ata.owner.as_ref(),
+        ],
+        bump,
+        space = 8 + ActivationTokenReceiptState::MAX_SIZE,
+    )]
+    pub receiver_receipt: Box<Account<'info, ActivationTokenReceiptState>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
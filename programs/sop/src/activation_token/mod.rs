@@ -3,3 +3,7 @@ pub use instructions::*;
 
 pub mod activation_token_state;
 pub use activation_token_state::ActivationTokenState;
+pub mod activation_token_issue_state;
+pub use activation_token_issue_state::ActivationTokenIssueState;
+pub mod activation_token_receipt_state;
+pub use activation_token_receipt_state::ActivationTokenReceiptState;
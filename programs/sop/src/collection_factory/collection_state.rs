@@ -4,8 +4,23 @@ use anchor_lang::prelude::*;
 pub struct CollectionState {
     pub genesis_profile: Pubkey,
     pub collection_id: Pubkey,
+    /// layout version, bumped by `migrate_collection_state`
+    pub version: u8,
+    /// set by `release_collection_authority` once `main_state` no longer holds its authority
+    pub released: bool,
 }
 
 impl CollectionState {
     pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
+    pub const VERSION: u8 = 2;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum CollectionType {
+    /// top level collection, not verified against a parent
+    Root,
+    Profile,
+    Badge,
+    ActivationToken,
+    Pass,
 }
@@ -17,9 +17,12 @@ use mpl_token_metadata::{
 use solana_program::program::{invoke, invoke_signed};
 
 use crate::{
-    _main::MainState,
-    collection_factory::CollectionState,
-    constants::{SEED_COLLECTION_STATE, SEED_MAIN_STATE, SEED_PROFILE_STATE},
+    _main::{MainState, Role, RoleState},
+    collection_factory::{CollectionState, CollectionType},
+    constants::{
+        PAUSE_COLLECTIONS, SEED_COLLECTION_STATE, SEED_MAIN_STATE, SEED_PROFILE_STATE,
+        SEED_ROLE_STATE,
+    },
     error::MyError,
     other_states::LineageInfo, utils::verify_collection_item_by_main,
 };
@@ -28,26 +31,25 @@ pub fn create_collection(
     ctx: Context<ACreateCollection>,
     name: String,
     symbol: String,
-    uri: String,
-    collection_type: String,
+    uri_hash: String,
+    collection_type: CollectionType,
 ) -> Result<()> {
+    let uri = ctx.accounts.main_state.collection_uri(&uri_hash)?;
     {
-        // Setup
+        // Setup, the collection becomes native through `set_native_collections`
         let collection_id = ctx.accounts.collection.key();
-        if collection_type == "profile" {
-            ctx.accounts.main_state.profile_collection = collection_id;
-        }
-
         ctx.accounts.collection_state.collection_id = collection_id;
+        ctx.accounts.collection_state.version = CollectionState::VERSION;
+        ctx.accounts.main_state.total_collections += 1;
     }
     {
-        ctx.accounts.mint(name, symbol, uri, collection_type.to_string())?;
+        ctx.accounts.mint(name, symbol, uri, collection_type)?;
     }
     {
         ctx.accounts.approve_collection_authority_to_main()?;
     }
     
-    if collection_type != "root" {
+    if collection_type != CollectionType::Root {
         ctx.accounts.verify_collection_item(ctx.program_id)?;
     }
 
@@ -55,17 +57,33 @@ pub fn create_collection(
 }
 
 #[derive(Accounts)]
+#[instruction(name: String, symbol: String, uri_hash: String, collection_type: CollectionType)]
 pub struct ACreateCollection<'info> {
-    #[account(mut, address = main_state.owner @ MyError::OnlyOwnerCanCall)]
+    #[account(
+        mut,
+        constraint = main_state.is_authorized(
+            &admin.key(),
+            &role_state,
+            Role::CollectionManager,
+        ) @ MyError::MissingRole,
+    )]
     pub admin: Signer<'info>,
 
     #[account(
         mut,
         seeds = [SEED_MAIN_STATE],
         bump,
+        constraint = !main_state.is_paused(PAUSE_COLLECTIONS) @ MyError::CollectionsPaused,
+        constraint = main_state.native_collection(collection_type) == System::id() @ MyError::AlreadySet,
     )]
     pub main_state: Box<Account<'info, MainState>>,
 
+    #[account(
+        seeds = [SEED_ROLE_STATE, Role::CollectionManager.seed(), admin.key().as_ref()],
+        bump = role_state._bump,
+    )]
+    pub role_state: Option<Box<Account<'info, RoleState>>>,
+
     #[account(
         mut,
         mint::decimals = 0,
@@ -178,7 +196,7 @@ pub struct ACreateCollection<'info> {
 }
 
 impl<'info> ACreateCollection<'info> {
-    pub fn mint(&mut self, name: String, symbol: String, uri: String, collection_type: String) -> Result<()> {
+    pub fn mint(&mut self, name: String, symbol: String, uri: String, collection_type: CollectionType) -> Result<()> {
         let mint = self.collection.to_account_info();
         let payer = self.admin.to_account_info();
         let ata = self.admin_ata.to_account_info();
@@ -223,7 +241,7 @@ impl<'info> ACreateCollection<'info> {
             seller_fee_basis_points: main_state.seller_fee_basis_points,
         };
 
-        if collection_type != "root" {
+        if collection_type != CollectionType::Root {
             asset_data.collection = Some(mpl_token_metadata::state::Collection {
                 verified: false,
                 key: self.parent_collection.key(),
@@ -0,0 +1,57 @@
+use crate::{
+    _main::main_state::MainState,
+    collection_factory::collection_state::CollectionState,
+    constants::{SEED_COLLECTION_STATE, SEED_MAIN_STATE},
+    error::MyError,
+    utils::migrate_account,
+};
+use anchor_lang::prelude::*;
+
+/// Grows an existing `CollectionState` to the current layout and stamps `CollectionState::VERSION`.
+/// Collections made before `MainState.total_collections` existed get counted, so
+/// `execute_reset` waits for their authority to be released too.
+pub fn migrate_collection_state(ctx: Context<AMigrateCollectionState>) -> Result<()> {
+    let mut uncounted = false;
+    migrate_account::<CollectionState>(
+        ctx.accounts.collection_state.to_account_info(),
+        ctx.accounts.owner.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        8 + CollectionState::MAX_SIZE,
+        |state| {
+            uncounted = state.version < 2;
+            state.version = CollectionState::VERSION;
+        },
+    )?;
+    if uncounted {
+        ctx.accounts.main_state.total_collections += 1;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AMigrateCollectionState<'info> {
+    #[account(mut, address = main_state.owner @ MyError::OnlyOwnerCanCall)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_MAIN_STATE],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    ///CHECK: only used as the collection state seed
+    pub collection: AccountInfo<'info>,
+
+    ///CHECK: deserialized in `migrate_collection_state` once it has the current size
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [SEED_COLLECTION_STATE, collection.key().as_ref()],
+        bump,
+    )]
+    pub collection_state: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -2,4 +2,6 @@ pub mod create_collection;
 pub use create_collection::*;
 
 pub mod update_collection;
-pub use update_collection::*;
\ No newline at end of file
+pub use update_collection::*;
+pub mod migrate_collection_state;
+pub use migrate_collection_state::*;
@@ -18,9 +18,12 @@ use mpl_token_metadata::{
 use solana_program::program::{invoke, invoke_signed};
 
 use crate::{
-    _main::MainState,
+    _main::{MainState, Role, RoleState},
     collection_factory::CollectionState,
-    constants::{SEED_COLLECTION_STATE, SEED_MAIN_STATE, SEED_PROFILE_STATE},
+    constants::{
+        PAUSE_COLLECTIONS, SEED_COLLECTION_STATE, SEED_MAIN_STATE, SEED_PROFILE_STATE,
+        SEED_ROLE_STATE,
+    },
     error::MyError,
     other_states::LineageInfo, utils::verify_collection_item_by_main,
 };
@@ -29,8 +32,9 @@ pub fn update_collection(
     ctx: Context<AUpdateCollection>,
     name: String,
     symbol: String,
-    uri: String,
+    uri_hash: String,
 ) -> Result<()> {
+    let uri = ctx.accounts.main_state.collection_uri(&uri_hash)?;
     {
         ctx.accounts.update(name, symbol, uri)?;
     }
@@ -44,16 +48,30 @@ pub fn update_collection(
 
 #[derive(Accounts)]
 pub struct AUpdateCollection<'info> {
-    #[account(mut, address = main_state.owner @ MyError::OnlyOwnerCanCall)]
+    #[account(
+        mut,
+        constraint = main_state.is_authorized(
+            &admin.key(),
+            &role_state,
+            Role::CollectionManager,
+        ) @ MyError::MissingRole,
+    )]
     pub admin: Signer<'info>,
 
     #[account(
         mut,
         seeds = [SEED_MAIN_STATE],
         bump,
+        constraint = !main_state.is_paused(PAUSE_COLLECTIONS) @ MyError::CollectionsPaused,
     )]
     pub main_state: Box<Account<'info, MainState>>,
 
+    #[account(
+        seeds = [SEED_ROLE_STATE, Role::CollectionManager.seed(), admin.key().as_ref()],
+        bump = role_state._bump,
+    )]
+    pub role_state: Option<Box<Account<'info, RoleState>>>,
+
     #[account(
         mut,
         mint::decimals = 0,
@@ -3,6 +3,48 @@ pub const SEED_PROFILE_STATE: &[u8] = b"profile_state1";
 pub const SEED_ACTIVATION_TOKEN_STATE: &[u8] = b"activation_token_state1";
 pub const SEED_VAULT: &[u8] = b"vault1";
 pub const SEED_COLLECTION_STATE: &[u8] = b"collection_state1";
+pub const SEED_ROLE_STATE: &[u8] = b"role_state1";
+pub const SEED_PRICING_STATE: &[u8] = b"pricing_state1";
+pub const SEED_CHILD_INDEX: &[u8] = b"child_index1";
+pub const SEED_PAYMENT_STATE: &[u8] = b"payment_state1";
+pub const SEED_ACTIVATION_TOKEN_ISSUE: &[u8] = b"activation_token_issue1";
+pub const SEED_ACTIVATION_TOKEN_RECEIPT: &[u8] = b"activation_token_receipt1";
+pub const SEED_GENERATION_STATE: &[u8] = b"generation_state1";
+
+/// deepest lineage `MainState.lineage_depth` can be set to
+pub const MAX_LINEAGE_DEPTH: usize = 8;
+
+/// mints `PaymentState` can accept besides OPOS
+pub const MAX_PAYMENT_MINTS: usize = 8;
+
+/// fixed point scale of `PaymentMint.rate`
+pub const PAYMENT_RATE_SCALE: u64 = 1_000_000_000;
+
+/// children per `ChildIndexState` page
+pub const CHILD_INDEX_PAGE_SIZE: usize = 32;
+
+/// room for URI prefixes and base URIs stored on `MainState`
+pub const MAX_URI_PREFIX_LEN: usize = 96;
+pub const MAX_URI_PREFIXES: usize = 4;
+
+/// longest `uri_hash` appended to a base URI
+pub const MAX_URI_HASH_LEN: usize = 64;
+
+/// base URI of profiles minted before it became configurable
+pub const LEGACY_PROFILE_BASE_URI: &str =
+    "https://shdw-drive.genesysgo.net/FuBjTTmQuqM7pGR2gFsaiBxDmdj8ExP5fzNwnZyE2PgC/";
 
 /// basically this amount equal to `100%`
 pub const TOTAL_SELLER_BASIS_POINTS: u16 = 10_000;
+
+/// `MainState.paused` flags, one per subsystem
+pub const PAUSE_PROFILES: u8 = 1 << 0;
+pub const PAUSE_ACTIVATION_TOKENS: u8 = 1 << 1;
+pub const PAUSE_COLLECTIONS: u8 = 1 << 2;
+pub const PAUSE_CURVE_BUY: u8 = 1 << 3;
+pub const PAUSE_CURVE_SELL: u8 = 1 << 4;
+pub const PAUSE_ALL: u8 =
+    PAUSE_PROFILES | PAUSE_ACTIVATION_TOKENS | PAUSE_COLLECTIONS | PAUSE_CURVE_BUY | PAUSE_CURVE_SELL;
+
+/// delay between `schedule_reset` and `execute_reset`
+pub const RESET_DELAY: i64 = 7 * 24 * 60 * 60;
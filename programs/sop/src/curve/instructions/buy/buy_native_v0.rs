@@ -1,4 +1,9 @@
-use crate::curve::state::*;
+use crate::{
+  _main::MainState,
+  constants::{PAUSE_CURVE_BUY, SEED_MAIN_STATE},
+  curve::{instructions::lineage_royalties::*, state::*},
+  error::MyError,
+};
 use anchor_lang::{
   prelude::*,
   solana_program::{program::invoke, system_instruction},
@@ -31,9 +36,22 @@ pub struct BuyNativeV0<'info> {
   #[account(mut)]
   pub sol_storage: SystemAccount<'info>,
   pub system_program: Program<'info, System>,
+  #[account(
+    seeds = [SEED_MAIN_STATE],
+    bump,
+    constraint = !main_state.is_paused(PAUSE_CURVE_BUY) @ MyError::CurveBuyPaused,
+  )]
+  pub main_state: Box<Account<'info, MainState>>,
+  /// CHECK: Profile state pda of the target mint, checked in `lineage_payouts`. Empty when the
+  /// bonding does not belong to a profile
+  pub profile_state: UncheckedAccount<'info>,
 }
 
-pub fn handler(ctx: Context<BuyNativeV0>, args: BuyV0Args) -> Result<()> {
+/// `remaining_accounts` holds the lineage accounts of profile bondings, see `lineage_payouts`
+pub fn handler<'info>(
+  ctx: Context<'_, '_, '_, 'info, BuyNativeV0<'info>>,
+  args: BuyV0Args,
+) -> Result<()> {
   let BuyAmount {
     price,
     base_royalties,
@@ -65,14 +83,31 @@ pub fn handler(ctx: Context<BuyNativeV0>, args: BuyV0Args) -> Result<()> {
 
   if base_royalties > 0 {
     // msg!("Paying out {} base royalties", base_royalties);
-    invoke(
-      &system_instruction::transfer(&source.key(), &base_royalties_account.key(), base_royalties),
-      &[
-        source.to_account_info().clone(),
-        base_royalties_account.to_account_info().clone(),
-        ctx.accounts.system_program.to_account_info().clone(),
-      ],
-    )?;
+    let payouts = lineage_payouts(
+      &ctx.accounts.main_state,
+      &ctx.accounts.profile_state,
+      ctx.remaining_accounts,
+      &ctx.accounts.common.target_mint.key(),
+      None,
+      base_royalties,
+    )?
+      .unwrap_or_else(|| {
+        vec![LineagePayout {
+          recipient: base_royalties_account.clone(),
+          amount: base_royalties,
+        }]
+      });
+
+    for payout in payouts {
+      invoke(
+        &system_instruction::transfer(&source.key(), &payout.recipient.key(), payout.amount),
+        &[
+          source.to_account_info().clone(),
+          payout.recipient.clone(),
+          ctx.accounts.system_program.to_account_info().clone(),
+        ],
+      )?;
+    }
   }
 
   // msg!("Paying out {} to base storage", price);
@@ -3,7 +3,12 @@ use super::{
   buy_arg_common::BuyV0Args,
   common::{buy_shared_logic, mint_to_dest, BuyAmount},
 };
-use crate::curve::state::*;
+use crate::{
+  _main::MainState,
+  constants::{PAUSE_CURVE_BUY, SEED_MAIN_STATE},
+  curve::{instructions::lineage_royalties::*, state::*},
+  error::MyError,
+};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, TokenAccount, Transfer};
 
@@ -18,9 +23,22 @@ pub struct BuyV1<'info> {
   #[account(mut)]
   pub source: Box<Account<'info, TokenAccount>>,
   pub source_authority: Signer<'info>,
+  #[account(
+    seeds = [SEED_MAIN_STATE],
+    bump,
+    constraint = !main_state.is_paused(PAUSE_CURVE_BUY) @ MyError::CurveBuyPaused,
+  )]
+  pub main_state: Box<Account<'info, MainState>>,
+  /// CHECK: Profile state pda of the target mint, checked in `lineage_payouts`. Empty when the
+  /// bonding does not belong to a profile
+  pub profile_state: UncheckedAccount<'info>,
 }
 
-pub fn handler(ctx: Context<BuyV1>, args: BuyV0Args) -> Result<()> {
+/// `remaining_accounts` holds the lineage accounts of profile bondings, see `lineage_payouts`
+pub fn handler<'info>(
+  ctx: Context<'_, '_, '_, 'info, BuyV1<'info>>,
+  args: BuyV0Args,
+) -> Result<()> {
   let BuyAmount {
     total_amount,
     price,
@@ -54,17 +72,34 @@ pub fn handler(ctx: Context<BuyV1>, args: BuyV0Args) -> Result<()> {
 
   if base_royalties > 0 {
     msg!("Paying out {} base royalties", base_royalties);
-    token::transfer(
-      CpiContext::new(
-        token_program.clone(),
-        Transfer {
-          from: source.clone(),
-          to: base_royalties_account.clone(),
-          authority: source_authority.clone(),
-        },
-      ),
+    let payouts = lineage_payouts(
+      &ctx.accounts.main_state,
+      &ctx.accounts.profile_state,
+      ctx.remaining_accounts,
+      &ctx.accounts.common.target_mint.key(),
+      Some(&ctx.accounts.common.base_mint.key()),
       base_royalties,
-    )?;
+    )?
+      .unwrap_or_else(|| {
+        vec![LineagePayout {
+          recipient: base_royalties_account.clone(),
+          amount: base_royalties,
+        }]
+      });
+
+    for payout in payouts {
+      token::transfer(
+        CpiContext::new(
+          token_program.clone(),
+          Transfer {
+            from: source.clone(),
+            to: payout.recipient,
+            authority: source_authority.clone(),
+          },
+        ),
+        payout.amount,
+      )?;
+    }
   }
 
   msg!("Paying out {} to base storage", price);
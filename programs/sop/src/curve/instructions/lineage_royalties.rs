@@ -0,0 +1,324 @@
+use crate::{
+  _main::MainState,
+  constants::SEED_PROFILE_STATE,
+  curve::error::ErrorCode,
+  error::MyError,
+  profile::{record_earnings, EarningsSource, ProfileState},
+  utils::lineage_holder,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+/// Accounts of each lineage level in the `remaining_accounts` of a profile bonding trade
+pub const LINEAGE_LEVEL_ACCOUNTS: usize = 3;
+
+pub struct LineagePayout<'info> {
+  pub recipient: AccountInfo<'info>,
+  pub amount: u64,
+}
+
+/// Splits the base royalties of a trade on `target_mint` across the profile's lineage using
+/// `MainState.lineage_distribution`. Returns `None` when the target mint is not a profile, in
+/// which case the bonding's own royalty account gets paid as before and no lineage accounts
+/// are needed.
+/// For a profile, `lineage_accounts` (the trade's `remaining_accounts`) hold for each of the
+/// first `MainState.lineage_depth` ancestors, parent first, and then genesis:
+/// - the profile state, its `current_holder` gets paid. The share of a retired ancestor,
+///   whose state is closed, goes to genesis
+/// - the royalties recipient, a base mint token account of the holder or its wallet when
+///   `base_mint` is `None` (native sol)
+/// - the earnings account of the profile, recording the royalties of OPOS trades
+pub fn lineage_payouts<'info>(
+  main_state: &MainState,
+  profile_state: &AccountInfo<'info>,
+  lineage_accounts: &[AccountInfo<'info>],
+  target_mint: &Pubkey,
+  base_mint: Option<&Pubkey>,
+  royalties: u64,
+) -> Result<Option<Vec<LineagePayout<'info>>>> {
+  let (profile_state_key, _) =
+    Pubkey::find_program_address(&[SEED_PROFILE_STATE, target_mint.as_ref()], &crate::ID);
+  require_keys_eq!(profile_state.key(), profile_state_key, MyError::UnknownNft);
+  if *profile_state.owner != crate::ID || profile_state.data_is_empty() {
+    return Ok(None);
+  }
+  let profile_state = ProfileState::try_deserialize(&mut &profile_state.try_borrow_data()?[..])?;
+
+  let mut lineage = main_state.lineage_split(&profile_state.ancestors, royalties)?;
+  require!(
+    lineage_accounts.len() == lineage.len() * LINEAGE_LEVEL_ACCOUNTS,
+    MyError::InvalidLineageAccounts
+  );
+
+  // the share of a retired ancestor goes to genesis, like its minting cost
+  let genesis_level = lineage.len() - 1;
+  let mut holders = Vec::with_capacity(lineage.len());
+  for (level, accounts) in lineage_accounts.chunks(LINEAGE_LEVEL_ACCOUNTS).enumerate() {
+    let holder = lineage_holder(&accounts[0], &lineage[level].0)?;
+    if holder.is_none() && level != genesis_level {
+      lineage[genesis_level].1 += lineage[level].1;
+      lineage[level].1 = 0;
+    }
+    holders.push(holder);
+  }
+
+  let mut payouts = Vec::with_capacity(lineage.len());
+  let mut earnings = Vec::with_capacity(lineage.len());
+  let mut earnings_accounts = Vec::with_capacity(lineage.len());
+  for (((profile, amount), accounts), holder) in lineage
+    .into_iter()
+    .zip(lineage_accounts.chunks(LINEAGE_LEVEL_ACCOUNTS))
+    .zip(holders)
+  {
+    if amount == 0 {
+      continue;
+    }
+    let (recipient, profile_earnings) = (&accounts[1], &accounts[2]);
+
+    let holder = holder.ok_or(MyError::InvalidLineageHolder)?;
+    match base_mint {
+      Some(base_mint) => {
+        let recipient_ata = Account::<TokenAccount>::try_from(recipient)?;
+        require_keys_eq!(recipient_ata.mint, *base_mint, ErrorCode::InvalidMint);
+        require_keys_eq!(recipient_ata.owner, holder, MyError::InvalidLineageHolder);
+      }
+      None => require_keys_eq!(recipient.key(), holder, MyError::InvalidLineageHolder),
+    }
+
+    payouts.push(LineagePayout {
+      recipient: recipient.clone(),
+      amount,
+    });
+    earnings.push((profile, EarningsSource::TradingRoyalty, amount));
+    earnings_accounts.push(profile_earnings.clone());
+  }
+
+  if base_mint == Some(&main_state.opos_token) {
+    record_earnings(&earnings_accounts, &earnings)?;
+  }
+
+  Ok(Some(payouts))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{constants::MAX_LINEAGE_DEPTH, other_states::LineageDistribution};
+  use anchor_spl::token::spl_token::{
+    self,
+    solana_program::program_pack::Pack,
+    state::{Account as SplTokenAccount, AccountState},
+  };
+
+  struct TestAccount {
+    key: Pubkey,
+    owner: Pubkey,
+    lamports: u64,
+    data: Vec<u8>,
+  }
+
+  impl TestAccount {
+    fn new(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> Self {
+      Self {
+        key,
+        owner,
+        lamports: 1,
+        data,
+      }
+    }
+
+    fn token(mint: Pubkey, owner: Pubkey) -> Self {
+      let mut data = vec![0; SplTokenAccount::LEN];
+      SplTokenAccount {
+        mint,
+        owner,
+        state: AccountState::Initialized,
+        ..Default::default()
+      }
+      .pack_into_slice(&mut data);
+      Self::new(Pubkey::new_unique(), spl_token::ID, data)
+    }
+
+    fn info(&mut self) -> AccountInfo<'_> {
+      AccountInfo::new(
+        &self.key,
+        false,
+        true,
+        &mut self.lamports,
+        &mut self.data,
+        &self.owner,
+        false,
+        0,
+      )
+    }
+  }
+
+  fn profile_state_key(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[SEED_PROFILE_STATE, mint.as_ref()], &crate::ID).0
+  }
+
+  fn main_state(genesis_profile: Pubkey) -> MainState {
+    let mut main_state = MainState::deserialize(&mut &[0u8; MainState::MAX_SIZE][..]).unwrap();
+    main_state.genesis_profile = genesis_profile;
+    main_state.lineage_depth = 3;
+    main_state.lineage_distribution =
+      LineageDistribution::new(&[3_000, 2_000, 1_000], 4_000).unwrap();
+    main_state
+  }
+
+  fn profile_state(mint: &Pubkey, lineage: &[Pubkey; 4], holder: Pubkey) -> TestAccount {
+    let mut state = ProfileState::deserialize(&mut &[0u8; ProfileState::MAX_SIZE][..]).unwrap();
+    state.mint = *mint;
+    state.ancestors = [lineage[3]; MAX_LINEAGE_DEPTH];
+    state.ancestors[..3].copy_from_slice(&lineage[..3]);
+    state.current_holder = holder;
+    let mut data = Vec::new();
+    state.try_serialize(&mut data).unwrap();
+    TestAccount::new(profile_state_key(mint), crate::ID, data)
+  }
+
+  /// profile state, recipient and earnings account of each lineage level
+  fn lineage_accounts(
+    lineage: &[Pubkey; 4],
+    holders: &[Pubkey; 4],
+    base_mint: Option<Pubkey>,
+  ) -> Vec<TestAccount> {
+    lineage
+      .iter()
+      .zip(holders)
+      .flat_map(|(profile, holder)| {
+        let recipient = match base_mint {
+          Some(base_mint) => TestAccount::token(base_mint, *holder),
+          None => TestAccount::new(*holder, System::id(), vec![]),
+        };
+        [
+          profile_state(profile, &[*profile; 4], *holder),
+          recipient,
+          TestAccount::new(Pubkey::new_unique(), crate::ID, vec![]),
+        ]
+      })
+      .collect()
+  }
+
+  #[test]
+  fn non_profile_trade_pays_bonding_royalties() {
+    let target_mint = Pubkey::new_unique();
+    let main_state = main_state(Pubkey::new_unique());
+    let mut state = TestAccount::new(profile_state_key(&target_mint), System::id(), vec![]);
+
+    let payouts = lineage_payouts(&main_state, &state.info(), &[], &target_mint, None, 1_000);
+    assert!(payouts.unwrap().is_none());
+
+    let mut state = TestAccount::new(Pubkey::new_unique(), System::id(), vec![]);
+    let payouts = lineage_payouts(&main_state, &state.info(), &[], &target_mint, None, 1_000);
+    assert!(payouts.is_err());
+  }
+
+  #[test]
+  fn profile_trade_pays_lineage_holders() {
+    let target_mint = Pubkey::new_unique();
+    let lineage = [(); 4].map(|_| Pubkey::new_unique());
+    let holders = [(); 4].map(|_| Pubkey::new_unique());
+    let main_state = main_state(lineage[3]);
+    let mut state = profile_state(&target_mint, &lineage, Pubkey::new_unique());
+
+    for base_mint in [None, Some(Pubkey::new_unique())] {
+      let mut accounts = lineage_accounts(&lineage, &holders, base_mint);
+      let expected_recipients = accounts
+        .iter()
+        .skip(1)
+        .step_by(3)
+        .map(|a| a.key)
+        .collect::<Vec<_>>();
+      let infos = accounts
+        .iter_mut()
+        .map(TestAccount::info)
+        .collect::<Vec<_>>();
+      let payouts = lineage_payouts(
+        &main_state,
+        &state.info(),
+        &infos,
+        &target_mint,
+        base_mint.as_ref(),
+        1_000,
+      )
+      .unwrap()
+      .unwrap();
+
+      assert_eq!(
+        payouts.iter().map(|p| p.amount).collect::<Vec<_>>(),
+        vec![300, 200, 100, 400]
+      );
+      assert_eq!(
+        payouts
+          .iter()
+          .map(|p| p.recipient.key())
+          .collect::<Vec<_>>(),
+        expected_recipients
+      );
+    }
+
+    // the grand parent retired, genesis gets its share
+    let mut accounts = lineage_accounts(&lineage, &holders, None);
+    accounts[3] = TestAccount::new(profile_state_key(&lineage[1]), System::id(), vec![]);
+    let infos = accounts
+      .iter_mut()
+      .map(TestAccount::info)
+      .collect::<Vec<_>>();
+    let payouts = lineage_payouts(
+      &main_state,
+      &state.info(),
+      &infos,
+      &target_mint,
+      None,
+      1_000,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(
+      payouts
+        .iter()
+        .map(|p| (p.recipient.key(), p.amount))
+        .collect::<Vec<_>>(),
+      vec![(holders[0], 300), (holders[2], 100), (holders[3], 600)]
+    );
+
+    // ancestors not backfilled yet
+    let mut unmigrated = profile_state(&target_mint, &[Pubkey::default(); 4], Pubkey::new_unique());
+    let mut accounts = lineage_accounts(&lineage, &holders, None);
+    let infos = accounts
+      .iter_mut()
+      .map(TestAccount::info)
+      .collect::<Vec<_>>();
+    let payouts = lineage_payouts(
+      &main_state,
+      &unmigrated.info(),
+      &infos,
+      &target_mint,
+      None,
+      1_000,
+    );
+    assert!(payouts.is_err());
+
+    // missing lineage accounts
+    let payouts = lineage_payouts(&main_state, &state.info(), &[], &target_mint, None, 1_000);
+    assert!(payouts.is_err());
+
+    // royalties routed to someone other than the parent's holder
+    let mut accounts = lineage_accounts(&lineage, &holders, None);
+    accounts[1].key = holders[1];
+    let infos = accounts
+      .iter_mut()
+      .map(TestAccount::info)
+      .collect::<Vec<_>>();
+    let payouts = lineage_payouts(
+      &main_state,
+      &state.info(),
+      &infos,
+      &target_mint,
+      None,
+      1_000,
+    );
+    assert!(payouts.is_err());
+  }
+}
@@ -3,6 +3,7 @@ pub mod close_token_bonding_v0;
 pub mod create_curve_v0;
 pub mod initialize_sol_storage_v0;
 pub mod initialize_token_bonding_v0;
+pub mod lineage_royalties;
 pub mod sell;
 pub mod transfer_reserves;
 pub mod update_curve_v0;
@@ -14,6 +15,7 @@ pub use close_token_bonding_v0::*;
 pub use create_curve_v0::*;
 pub use initialize_sol_storage_v0::*;
 pub use initialize_token_bonding_v0::*;
+pub use lineage_royalties::*;
 pub use sell::*;
 pub use transfer_reserves::*;
 pub use update_curve_v0::*;
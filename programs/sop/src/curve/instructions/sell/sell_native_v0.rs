@@ -4,7 +4,12 @@ use super::{
   sell_arg_common::SellV0Args,
   sell_wrapped_sol_v0::{sell_wrapped_sol, SellWrappedSolV0, SellWrappedSolV0Args},
 };
-use crate::curve::state::*;
+use crate::{
+  _main::MainState,
+  constants::{PAUSE_CURVE_SELL, SEED_MAIN_STATE},
+  curve::{instructions::lineage_royalties::*, state::*},
+  error::MyError,
+};
 use anchor_lang::prelude::*;
 use anchor_spl::token::Mint;
 
@@ -27,9 +32,22 @@ pub struct SellNativeV0<'info> {
   #[account(mut)]
   pub sol_storage: SystemAccount<'info>,
   pub system_program: Program<'info, System>,
+  #[account(
+    seeds = [SEED_MAIN_STATE],
+    bump,
+    constraint = !main_state.is_paused(PAUSE_CURVE_SELL) @ MyError::CurveSellPaused,
+  )]
+  pub main_state: Box<Account<'info, MainState>>,
+  /// CHECK: Profile state pda of the target mint, checked in `lineage_payouts`. Empty when the
+  /// bonding does not belong to a profile
+  pub profile_state: UncheckedAccount<'info>,
 }
 
-pub fn handler(ctx: Context<SellNativeV0>, args: SellV0Args) -> Result<()> {
+/// `remaining_accounts` holds the lineage accounts of profile bondings, see `lineage_payouts`
+pub fn handler<'info>(
+  ctx: Context<'_, '_, '_, 'info, SellNativeV0<'info>>,
+  args: SellV0Args,
+) -> Result<()> {
   let amount = args.target_amount;
 
   let SellAmount {
@@ -82,28 +100,47 @@ pub fn handler(ctx: Context<SellNativeV0>, args: SellV0Args) -> Result<()> {
     Some(bonding_seeds),
   )?;
 
-  if base_royalties > 0 && ctx.accounts.common.sell_base_royalties.lamports() > 0 {
-    msg!(
-      "Paying out {} from base storage to base royalties",
-      base_royalties
-    );
-    sell_wrapped_sol(
-      &SellWrappedSolV0 {
-        state: ctx.accounts.state.clone(),
-        wrapped_sol_mint: ctx.accounts.wrapped_sol_mint.clone(),
-        sol_storage: ctx.accounts.sol_storage.clone(),
-        source: base_storage_account.clone(),
-        owner: token_bonding.to_account_info(),
-        destination: SystemAccount::try_from(&ctx.accounts.common.sell_base_royalties)?,
-        token_program: ctx.accounts.common.token_program.clone(),
-        system_program: ctx.accounts.system_program.clone(),
-      },
-      &SellWrappedSolV0Args {
+  if base_royalties > 0 {
+    let payouts = match lineage_payouts(
+      &ctx.accounts.main_state,
+      &ctx.accounts.profile_state,
+      ctx.remaining_accounts,
+      &target_mint.key(),
+      None,
+      base_royalties,
+    )? {
+      Some(payouts) => payouts,
+      // Do not send if royalties account is closed.
+      None if ctx.accounts.common.sell_base_royalties.lamports() > 0 => vec![LineagePayout {
+        recipient: ctx.accounts.common.sell_base_royalties.to_account_info(),
         amount: base_royalties,
-        all: false,
-      },
-      Some(bonding_seeds),
-    )?;
+      }],
+      None => vec![],
+    };
+
+    for payout in payouts {
+      msg!(
+        "Paying out {} from base storage to base royalties",
+        payout.amount
+      );
+      sell_wrapped_sol(
+        &SellWrappedSolV0 {
+          state: ctx.accounts.state.clone(),
+          wrapped_sol_mint: ctx.accounts.wrapped_sol_mint.clone(),
+          sol_storage: ctx.accounts.sol_storage.clone(),
+          source: base_storage_account.clone(),
+          owner: token_bonding.to_account_info(),
+          destination: SystemAccount::try_from(&payout.recipient)?,
+          token_program: ctx.accounts.common.token_program.clone(),
+          system_program: ctx.accounts.system_program.clone(),
+        },
+        &SellWrappedSolV0Args {
+          amount: payout.amount,
+          all: false,
+        },
+        Some(bonding_seeds),
+      )?;
+    }
   }
 
   Ok(())
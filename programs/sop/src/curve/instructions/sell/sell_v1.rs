@@ -3,7 +3,12 @@ use super::{
   sell_account_common::*,
   sell_arg_common::SellV0Args,
 };
-use crate::curve::state::*;
+use crate::{
+  _main::MainState,
+  constants::{PAUSE_CURVE_SELL, SEED_MAIN_STATE},
+  curve::{instructions::lineage_royalties::*, state::*},
+  error::MyError,
+};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, TokenAccount, Transfer};
 
@@ -18,9 +23,22 @@ pub struct SellV1<'info> {
 
   #[account(mut)]
   pub destination: Box<Account<'info, TokenAccount>>,
+  #[account(
+    seeds = [SEED_MAIN_STATE],
+    bump,
+    constraint = !main_state.is_paused(PAUSE_CURVE_SELL) @ MyError::CurveSellPaused,
+  )]
+  pub main_state: Box<Account<'info, MainState>>,
+  /// CHECK: Profile state pda of the target mint, checked in `lineage_payouts`. Empty when the
+  /// bonding does not belong to a profile
+  pub profile_state: UncheckedAccount<'info>,
 }
 
-pub fn handler(ctx: Context<SellV1>, args: SellV0Args) -> Result<()> {
+/// `remaining_accounts` holds the lineage accounts of profile bondings, see `lineage_payouts`
+pub fn handler<'info>(
+  ctx: Context<'_, '_, '_, 'info, SellV1<'info>>,
+  args: SellV0Args,
+) -> Result<()> {
   let SellAmount {
     reclaimed,
     base_royalties,
@@ -71,23 +89,35 @@ pub fn handler(ctx: Context<SellV1>, args: SellV0Args) -> Result<()> {
       "Paying out {} from base storage to base royalties",
       base_royalties
     );
-    token::transfer(
-      CpiContext::new_with_signer(
-        token_program.clone(),
-        Transfer {
-          from: base_storage_account.clone(),
-          to: ctx
-            .accounts
-            .common
-            .sell_base_royalties
-            .to_account_info()
-            .clone(),
-          authority: token_bonding.to_account_info().clone(),
-        },
-        bonding_seeds,
-      ),
+    let payouts = lineage_payouts(
+      &ctx.accounts.main_state,
+      &ctx.accounts.profile_state,
+      ctx.remaining_accounts,
+      &target_mint.key(),
+      Some(&ctx.accounts.common.base_mint.key()),
       base_royalties,
-    )?;
+    )?
+      .unwrap_or_else(|| {
+        vec![LineagePayout {
+          recipient: ctx.accounts.common.sell_base_royalties.to_account_info(),
+          amount: base_royalties,
+        }]
+      });
+
+    for payout in payouts {
+      token::transfer(
+        CpiContext::new_with_signer(
+          token_program.clone(),
+          Transfer {
+            from: base_storage_account.clone(),
+            to: payout.recipient,
+            authority: token_bonding.to_account_info().clone(),
+          },
+          bonding_seeds,
+        ),
+        payout.amount,
+      )?;
+    }
   }
 
   Ok(())
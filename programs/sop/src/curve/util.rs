@@ -1,5 +1,7 @@
+use crate::curve::curve::Curve;
 use crate::curve::error::ErrorCode;
 use crate::curve::precise_number::{InnerUint, PreciseNumber};
+use crate::curve::state::{CurveV0, TokenBondingV0};
 use anchor_lang::solana_program::system_program;
 use anchor_lang::{prelude::*, solana_program};
 use anchor_spl::token::{Mint, TokenAccount};
@@ -115,6 +117,40 @@ pub fn to_mint_amount(amt: &PreciseNumber, mint: &Mint, ceil: bool) -> u64 {
   post_round.to_imprecise().unwrap() as u64
 }
 
+/// Target amount `base_amount` buys on `token_bonding` right now, before royalties
+pub fn target_amount_for_base(
+  token_bonding: &TokenBondingV0,
+  curve: &CurveV0,
+  base_storage: &TokenAccount,
+  base_mint: &Mint,
+  target_mint: &Mint,
+  base_amount: u64,
+  unix_timestamp: i64,
+) -> Result<u64> {
+  let reserves = if token_bonding.ignore_external_reserve_changes {
+    token_bonding.reserve_balance_from_bonding
+  } else {
+    base_storage.amount
+  };
+  let supply = if token_bonding.ignore_external_supply_changes {
+    token_bonding.supply_from_bonding
+  } else {
+    target_mint.supply
+  };
+  let amount_prec = curve
+    .definition
+    .expected_target_amount(
+      unix_timestamp
+        .checked_sub(token_bonding.go_live_unix_time)
+        .ok_or(ErrorCode::ArithmeticError)?,
+      &precise_supply_amt(reserves, base_mint),
+      &precise_supply_amt(supply, target_mint),
+      &precise_supply_amt(base_amount, base_mint),
+    )
+    .or_arith_error()?;
+  Ok(to_mint_amount(&amount_prec, target_mint, false))
+}
+
 pub fn verify_empty_or_mint<'info>(
   maybe_token_account: &UncheckedAccount<'info>,
   mint: &Pubkey,
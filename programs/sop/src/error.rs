@@ -34,4 +34,160 @@ pub enum MyError {
 
     #[msg("Profile ID missmatch")]
     ProfileIdMissMatch,
+
+    #[msg("Minting cost distribution must add up to 100%")]
+    InvalidMintingCostDistribution,
+
+    #[msg("Trading price distribution must add up to 100%")]
+    InvalidTradingPriceDistribution,
+
+    #[msg("This method can only be called by pending owner")]
+    OnlyPendingOwnerCanCall,
+
+    #[msg("This method can only be called by owner or a granted role")]
+    MissingRole,
+
+    #[msg("Profile minting is paused")]
+    ProfilesPaused,
+
+    #[msg("Activation tokens are paused")]
+    ActivationTokensPaused,
+
+    #[msg("Collections are paused")]
+    CollectionsPaused,
+
+    #[msg("Curve buy is paused")]
+    CurveBuyPaused,
+
+    #[msg("Curve sell is paused")]
+    CurveSellPaused,
+
+    #[msg("Reset is not scheduled")]
+    ResetNotScheduled,
+
+    #[msg("Reset is scheduled")]
+    ResetScheduled,
+
+    #[msg("Reset timelock has not passed yet")]
+    ResetTimelockActive,
+
+    #[msg("Collection authorities must be released first")]
+    CollectionAuthorityNotReleased,
+
+    #[msg("Collection authorities have already been released")]
+    CollectionAuthorityReleased,
+
+    #[msg("Price tiers must start at generation 0 and be sorted")]
+    InvalidPriceTiers,
+
+    #[msg("Minimum invitation price is above the maximum")]
+    InvalidInvitationPriceBounds,
+
+    #[msg("Invitation price is out of bounds")]
+    InvitationPriceOutOfBounds,
+
+    #[msg("Account is not the current holder of the lineage profile")]
+    InvalidLineageHolder,
+
+    #[msg("Lineage distribution must add up to 100% within the max depth")]
+    InvalidLineageDistribution,
+
+    #[msg("Expected accounts for every ancestor up to the lineage depth")]
+    InvalidLineageAccounts,
+
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+
+    #[msg("Genesis profile can not be retired")]
+    CannotRetireGenesis,
+
+    #[msg("Profile is not retired")]
+    ProfileNotRetired,
+
+    #[msg("Profile is not an ancestor")]
+    NotAnAncestor,
+
+    #[msg("Imported lineage does not match the parent profile")]
+    InvalidImportLineage,
+
+    #[msg("Metadata constraints exceed the token metadata limits")]
+    InvalidMetadataConstraints,
+
+    #[msg("Name is too long")]
+    NameTooLong,
+
+    #[msg("Symbol is too long")]
+    SymbolTooLong,
+
+    #[msg("Uri does not start with an allowed prefix")]
+    UriNotAllowed,
+
+    #[msg("Base uri is not set")]
+    BaseUriNotSet,
+
+    #[msg("Uri hash is empty, too long or has invalid characters")]
+    InvalidUriHash,
+
+    #[msg("Lookup table address does not match")]
+    InvalidLookupTable,
+
+    #[msg("Payment mints can only be appended, need a rate or a curve and must be unique")]
+    InvalidPaymentMints,
+
+    #[msg("Payment mint is not accepted")]
+    PaymentMintNotAccepted,
+
+    #[msg("Payment accounts are missing or don't match the payment mint")]
+    InvalidPaymentAccounts,
+
+    #[msg("Earnings in other payment mints have to be claimed first")]
+    UnclaimedPaymentEarnings,
+
+    #[msg("Expiry must be in the future")]
+    InvalidExpiry,
+
+    #[msg("Activation tokens are expired")]
+    ActivationTokensExpired,
+
+    #[msg("Activation tokens are revoked")]
+    ActivationTokensRevoked,
+
+    #[msg("Activation token issue account is missing or doesn't match")]
+    InvalidActivationTokenIssue,
+
+    #[msg("Invitation window limit needs a window length")]
+    InvalidInvitationQuota,
+
+    #[msg("Invitation quota exceeded")]
+    InvitationQuotaExceeded,
+
+    #[msg("Too many invitations in the current window")]
+    InvitationRateLimited,
+
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+
+    #[msg("Unknown pause flags")]
+    InvalidPauseFlags,
+
+    #[msg("Every invitation of this issue has been used")]
+    ActivationTokenIssueRedeemed,
+
+    #[msg("Not enough invitations of this issue")]
+    NotEnoughActivationTokens,
+
+    #[msg("Invitations can't be transferred to their own holder")]
+    ActivationTokenSelfTransfer,
+
+    #[msg("Legacy invitations can only be imported before the first new issue")]
+    LegacyActivationTokensClosed,
+
+    #[msg("Main state token accounts still hold unclaimed earnings")]
+    UnclaimedVaultBalance,
+
+    #[msg("Profile ancestors need migrate_profile_state first")]
+    LineageNotMigrated,
+
+    #[msg("Payment is above the max payment, curve priced payments need one")]
+    MaxPaymentExceeded,
 }
@@ -44,12 +44,149 @@ pub mod sop {
         Ok(())
     }
 
+    pub fn accept_main_state_ownership(ctx: Context<AAcceptMainStateOwnership>) -> Result<()> {
+        _main::accept_main_state_ownership(ctx)?;
+        Ok(())
+    }
+
+    pub fn cancel_ownership_transfer(ctx: Context<AUpdateMainStateOwner>) -> Result<()> {
+        _main::cancel_ownership_transfer(ctx)?;
+        Ok(())
+    }
+
+    pub fn migrate_main_state(ctx: Context<AMigrateMainState>) -> Result<()> {
+        _main::migrate_main_state(ctx)?;
+        Ok(())
+    }
+
+    pub fn migrate_profile_state(ctx: Context<AMigrateProfileState>) -> Result<()> {
+        profile::migrate_profile_state(ctx)?;
+        Ok(())
+    }
+
+    pub fn migrate_activation_token_state(
+        ctx: Context<AMigrateActivationTokenState>,
+    ) -> Result<()> {
+        activation_token::migrate_activation_token_state(ctx)?;
+        Ok(())
+    }
+
+    pub fn import_legacy_activation_tokens(
+        ctx: Context<AImportLegacyActivationTokens>,
+    ) -> Result<()> {
+        activation_token::import_legacy_activation_tokens(ctx)?;
+        Ok(())
+    }
+
+    pub fn migrate_collection_state(ctx: Context<AMigrateCollectionState>) -> Result<()> {
+        collection_factory::migrate_collection_state(ctx)?;
+        Ok(())
+    }
+
+    pub fn grant_role(ctx: Context<AGrantRole>, role: Role) -> Result<()> {
+        _main::grant_role(ctx, role)?;
+        Ok(())
+    }
+
+    pub fn revoke_role(ctx: Context<ARevokeRole>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn pause(ctx: Context<APause>, flags: u8) -> Result<()> {
+        _main::pause(ctx, flags)?;
+        Ok(())
+    }
+
+    pub fn unpause(ctx: Context<APause>, flags: u8) -> Result<()> {
+        _main::unpause(ctx, flags)?;
+        Ok(())
+    }
+
     pub fn set_common_lut(ctx: Context<AUpdateMainState>, lut: Pubkey) -> Result<()> {
         ctx.accounts.main_state.common_lut = lut;
         Ok(())
     }
 
-    pub fn reset_main(ctx: Context<AResetMain>) -> Result<()> {
+    pub fn set_lineage_config(
+        ctx: Context<ASetLineageConfig>,
+        input: LineageConfigInput,
+    ) -> Result<()> {
+        _main::set_lineage_config(ctx, input)?;
+        Ok(())
+    }
+
+    pub fn set_invitation_quota(
+        ctx: Context<ASetInvitationQuota>,
+        input: InvitationQuotaInput,
+    ) -> Result<()> {
+        _main::set_invitation_quota(ctx, input)?;
+        Ok(())
+    }
+
+    pub fn set_metadata_constraints(
+        ctx: Context<ASetMetadataConstraints>,
+        input: MetadataConstraintsInput,
+    ) -> Result<()> {
+        _main::set_metadata_constraints(ctx, input)?;
+        Ok(())
+    }
+
+    pub fn set_base_uris(ctx: Context<ASetBaseUris>, input: BaseUrisInput) -> Result<()> {
+        _main::set_base_uris(ctx, input)?;
+        Ok(())
+    }
+
+    pub fn init_pricing_state(
+        ctx: Context<AInitPricingState>,
+        input: PricingInput,
+    ) -> Result<()> {
+        _main::init_pricing_state(ctx, input)?;
+        Ok(())
+    }
+
+    pub fn update_pricing_state(
+        ctx: Context<AUpdatePricingState>,
+        input: PricingInput,
+    ) -> Result<()> {
+        _main::update_pricing_state(ctx, input)?;
+        Ok(())
+    }
+
+    pub fn init_payment_state(
+        ctx: Context<AInitPaymentState>,
+        input: PaymentInput,
+    ) -> Result<()> {
+        _main::init_payment_state(ctx, input)?;
+        Ok(())
+    }
+
+    pub fn update_payment_state(
+        ctx: Context<AUpdatePaymentState>,
+        input: PaymentInput,
+    ) -> Result<()> {
+        _main::update_payment_state(ctx, input)?;
+        Ok(())
+    }
+
+    pub fn schedule_reset(ctx: Context<AScheduleReset>) -> Result<()> {
+        _main::schedule_reset(ctx)?;
+        Ok(())
+    }
+
+    pub fn cancel_reset(ctx: Context<ACancelReset>) -> Result<()> {
+        _main::cancel_reset(ctx)?;
+        Ok(())
+    }
+
+    pub fn release_collection_authority(ctx: Context<AReleaseCollectionAuthority>) -> Result<()> {
+        _main::release_collection_authority(ctx)?;
+        Ok(())
+    }
+
+    pub fn execute_reset<'info>(
+        ctx: Context<'_, '_, '_, 'info, AExecuteReset<'info>>,
+    ) -> Result<()> {
+        _main::execute_reset(ctx)?;
         Ok(())
     }
 
@@ -57,10 +194,18 @@ pub mod sop {
         ctx: Context<ACreateCollection>,
         name: String,
         symbol: String,
-        uri: String,
-        collection_type: String
+        uri_hash: String,
+        collection_type: CollectionType,
+    ) -> Result<()> {
+        collection_factory::create_collection(ctx, name, symbol, uri_hash, collection_type)?;
+        Ok(())
+    }
+
+    pub fn set_native_collections<'info>(
+        ctx: Context<'_, '_, '_, 'info, ASetNativeCollection<'info>>,
+        input: NativeCollectionsInput,
     ) -> Result<()> {
-        collection_factory::create_collection(ctx, name, symbol, uri, collection_type)?;
+        _main::set_native_collections(ctx, input)?;
         Ok(())
     }
 
@@ -68,10 +213,10 @@ pub mod sop {
         ctx: Context<AUpdateCollection>,
         name: String,
         symbol: String,
-        uri: String,
+        uri_hash: String,
     ) -> Result<()> {
 
-        collection_factory::update_collection(ctx, name, symbol, uri )?;
+        collection_factory::update_collection(ctx, name, symbol, uri_hash )?;
         Ok(())
     }
 
@@ -84,15 +229,79 @@ pub mod sop {
         Ok(())
     }
 
+    pub fn import_profile(ctx: Context<AImportProfile>, input: ImportProfileInput) -> Result<()> {
+        profile::import_profile(ctx, input)?;
+        Ok(())
+    }
+
     //User calls
-    pub fn mint_profile_by_at(
-        ctx: Context<AMintProfileByAt>,
+    pub fn mint_profile_by_at<'info>(
+        ctx: Context<'_, '_, '_, 'info, AMintProfileByAt<'info>>,
         name: Box<String>,
         symbol: Box<String>,
         // uri: Box<String>,
         uri_hash: Box<String>,
+        max_payment: Option<u64>,
+    ) -> Result<()> {
+        profile::mint_profile_by_at(ctx, name, symbol, uri_hash, max_payment)?;
+        Ok(())
+    }
+
+    pub fn init_earnings(ctx: Context<AInitEarnings>) -> Result<()> {
+        profile::init_earnings(ctx)?;
+        Ok(())
+    }
+
+    pub fn claim_earnings(ctx: Context<AClaimEarnings>) -> Result<()> {
+        profile::claim_earnings(ctx)?;
+        Ok(())
+    }
+
+    pub fn claim_payment_earnings(ctx: Context<AClaimPaymentEarnings>, index: u8) -> Result<()> {
+        profile::claim_payment_earnings(ctx, index)?;
+        Ok(())
+    }
+
+    pub fn transfer_profile(ctx: Context<ATransferProfile>) -> Result<()> {
+        profile::transfer_profile(ctx)?;
+        Ok(())
+    }
+
+    pub fn sync_profile_holder(ctx: Context<ASyncProfileHolder>) -> Result<()> {
+        profile::sync_profile_holder(ctx)?;
+        Ok(())
+    }
+
+    pub fn init_profile_lut<'info>(
+        ctx: Context<'_, '_, '_, 'info, AProfileLut<'info>>,
+        recent_slot: u64,
     ) -> Result<()> {
-        profile::mint_profile_by_at(ctx, name, symbol, uri_hash)?;
+        profile::init_profile_lut(ctx, recent_slot)?;
+        Ok(())
+    }
+
+    pub fn extend_profile_lut<'info>(
+        ctx: Context<'_, '_, '_, 'info, AProfileLut<'info>>,
+    ) -> Result<()> {
+        profile::extend_profile_lut(ctx)?;
+        Ok(())
+    }
+
+    pub fn update_profile_metadata(
+        ctx: Context<AUpdateProfileMetadata>,
+        input: UpdateProfileMetadataInput,
+    ) -> Result<()> {
+        profile::update_profile_metadata(ctx, input)?;
+        Ok(())
+    }
+
+    pub fn retire_profile(ctx: Context<ARetireProfile>) -> Result<()> {
+        profile::retire_profile(ctx)?;
+        Ok(())
+    }
+
+    pub fn reparent_profile(ctx: Context<AReparentProfile>) -> Result<()> {
+        profile::reparent_profile(ctx)?;
         Ok(())
     }
 
@@ -100,14 +309,39 @@ pub mod sop {
         ctx: Context<AInitActivationToken>,
         name: String,
         symbol: String,
-        uri: String
+        uri_hash: String
     ) -> Result<()> {
-        activation_token::init_activation_token(ctx, name, symbol, uri)?;
+        activation_token::init_activation_token(ctx, name, symbol, uri_hash)?;
         Ok(())
     }
 
-    pub fn mint_activation_token(ctx: Context<AMintActivationToken>, amount: u64) -> Result<()> {
-        activation_token::mint_activation_token(ctx, amount)?;
+    pub fn mint_activation_token<'info>(
+        ctx: Context<'_, '_, '_, 'info, AMintActivationToken<'info>>,
+        amount: u64,
+        expires_at: Option<i64>,
+        max_payment: Option<u64>,
+    ) -> Result<()> {
+        activation_token::mint_activation_token(ctx, amount, expires_at, max_payment)?;
+        Ok(())
+    }
+
+    pub fn revoke_activation_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, ARevokeActivationTokens<'info>>,
+    ) -> Result<()> {
+        activation_token::revoke_activation_tokens(ctx)?;
+        Ok(())
+    }
+
+    pub fn transfer_activation_tokens(
+        ctx: Context<ATransferActivationTokens>,
+        amount: u64,
+    ) -> Result<()> {
+        activation_token::transfer_activation_tokens(ctx, amount)?;
+        Ok(())
+    }
+
+    pub fn set_invitation_price(ctx: Context<ASetInvitationPrice>, price: u64) -> Result<()> {
+        activation_token::set_invitation_price(ctx, price)?;
         Ok(())
     }
 
@@ -182,19 +416,31 @@ pub mod sop {
         curve::instructions::update_token_bonding_v0::handler(ctx, args)
       }
     
-      pub fn buy_v1(ctx: Context<BuyV1>, args: BuyV0Args) -> Result<()> {
+      pub fn buy_v1<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyV1<'info>>,
+        args: BuyV0Args,
+      ) -> Result<()> {
         curve::instructions::buy::buy_v1::handler(ctx, args)
       }
     
-      pub fn buy_native_v0(ctx: Context<BuyNativeV0>, args: BuyV0Args) -> Result<()> {
+      pub fn buy_native_v0<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuyNativeV0<'info>>,
+        args: BuyV0Args,
+      ) -> Result<()> {
         curve::instructions::buy::buy_native_v0::handler(ctx, args)
       }
     
-      pub fn sell_v1(ctx: Context<SellV1>, args: SellV0Args) -> Result<()> {
+      pub fn sell_v1<'info>(
+        ctx: Context<'_, '_, '_, 'info, SellV1<'info>>,
+        args: SellV0Args,
+      ) -> Result<()> {
         curve::instructions::sell::sell_v1::handler(ctx, args)
       }
     
-      pub fn sell_native_v0(ctx: Context<SellNativeV0>, args: SellV0Args) -> Result<()> {
+      pub fn sell_native_v0<'info>(
+        ctx: Context<'_, '_, '_, 'info, SellNativeV0<'info>>,
+        args: SellV0Args,
+      ) -> Result<()> {
         curve::instructions::sell::sell_native_v0::handler(ctx, args)
       }
 
@@ -10,5 +10,19 @@ pub struct LineageInfo {
     pub great_grand_parent: Pubkey,
     pub ggreat_grand_parent: Pubkey,
     pub generation: u64,
+    /// children ever minted, the next child's slot in the `ChildIndexState` pages. Retiring a
+    /// child doesn't lower it, that would hand its slot to the next child
     pub total_child: u64,
 }
+
+impl LineageInfo {
+    /// whether `self` is a valid lineage for a fresh child of `parent_mint`
+    pub fn is_child_of(&self, parent_mint: Pubkey, parent: &LineageInfo) -> bool {
+        self.parent == parent_mint
+            && self.grand_parent == parent.parent
+            && self.great_grand_parent == parent.grand_parent
+            && self.ggreat_grand_parent == parent.great_grand_parent
+            && self.generation == parent.generation + 1
+            && self.total_child == 0
+    }
+}
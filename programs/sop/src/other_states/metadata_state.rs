@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use anchor_lang::{AnchorDeserialize, AnchorSerialize};
+use mpl_token_metadata::state::{MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH};
+
+use crate::{
+    constants::{MAX_URI_HASH_LEN, MAX_URI_PREFIXES, MAX_URI_PREFIX_LEN},
+    error::MyError,
+};
+
+/// URI prefix stored inline, the first `len` bytes are used
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct UriPrefix {
+    pub len: u8,
+    pub bytes: [u8; MAX_URI_PREFIX_LEN],
+}
+
+impl Default for UriPrefix {
+    fn default() -> Self {
+        Self {
+            len: 0,
+            bytes: [0; MAX_URI_PREFIX_LEN],
+        }
+    }
+}
+
+impl UriPrefix {
+    pub fn new(prefix: &str) -> Result<Self> {
+        require!(
+            prefix.len() <= MAX_URI_PREFIX_LEN,
+            MyError::InvalidMetadataConstraints
+        );
+        let mut uri_prefix = Self::default();
+        uri_prefix.len = prefix.len() as u8;
+        uri_prefix.bytes[..prefix.len()].copy_from_slice(prefix.as_bytes());
+        Ok(uri_prefix)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// the full uri of `uri_hash` when used as a base uri
+    pub fn join(&self, uri_hash: &str) -> Result<String> {
+        require!(!self.is_empty(), MyError::BaseUriNotSet);
+        require!(
+            !uri_hash.is_empty()
+                && uri_hash.len() <= MAX_URI_HASH_LEN
+                && uri_hash
+                    .bytes()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, b'.' | b'_' | b'-')),
+            MyError::InvalidUriHash
+        );
+        let base = std::str::from_utf8(self.as_bytes()).map_err(|_| MyError::BaseUriNotSet)?;
+        Ok(format!("{}{}", base, uri_hash))
+    }
+}
+
+/// What `update_profile_metadata` lets a holder set
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Copy)]
+pub struct MetadataConstraints {
+    pub max_name_len: u8,
+    pub max_symbol_len: u8,
+    /// a new uri has to start with one of these, no uri changes when all are empty
+    pub uri_prefixes: [UriPrefix; MAX_URI_PREFIXES],
+}
+
+impl MetadataConstraints {
+    /// token metadata limits, no uri changes
+    pub fn new_default() -> Self {
+        Self {
+            max_name_len: MAX_NAME_LENGTH as u8,
+            max_symbol_len: MAX_SYMBOL_LENGTH as u8,
+            uri_prefixes: Default::default(),
+        }
+    }
+
+    pub fn new(max_name_len: u8, max_symbol_len: u8, uri_prefixes: &[String]) -> Result<Self> {
+        require!(
+            max_name_len as usize <= MAX_NAME_LENGTH
+                && max_symbol_len as usize <= MAX_SYMBOL_LENGTH
+                && uri_prefixes.len() <= MAX_URI_PREFIXES,
+            MyError::InvalidMetadataConstraints
+        );
+        let mut constraints = Self {
+            max_name_len,
+            max_symbol_len,
+            uri_prefixes: Default::default(),
+        };
+        for (slot, prefix) in constraints.uri_prefixes.iter_mut().zip(uri_prefixes) {
+            *slot = UriPrefix::new(prefix)?;
+        }
+        Ok(constraints)
+    }
+
+    pub fn check_name(&self, name: &str) -> Result<()> {
+        require!(name.len() <= self.max_name_len as usize, MyError::NameTooLong);
+        Ok(())
+    }
+
+    pub fn check_symbol(&self, symbol: &str) -> Result<()> {
+        require!(
+            symbol.len() <= self.max_symbol_len as usize,
+            MyError::SymbolTooLong
+        );
+        Ok(())
+    }
+
+    pub fn check_uri(&self, uri: &str) -> Result<()> {
+        require!(
+            self.uri_prefixes
+                .iter()
+                .any(|prefix| !prefix.is_empty() && uri.as_bytes().starts_with(prefix.as_bytes())),
+            MyError::UriNotAllowed
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_metadata_against_constraints() {
+        let constraints =
+            MetadataConstraints::new(8, 4, &["https://arweave.net/".to_string()]).unwrap();
+        assert!(constraints.check_name("profile1").is_ok());
+        assert!(constraints.check_name("profile12").is_err());
+        assert!(constraints.check_symbol("SOP").is_ok());
+        assert!(constraints.check_symbol("SOPSOP").is_err());
+        assert!(constraints.check_uri("https://arweave.net/abc").is_ok());
+        assert!(constraints.check_uri("https://example.com/abc").is_err());
+
+        assert!(MetadataConstraints::new_default()
+            .check_uri("https://arweave.net/abc")
+            .is_err());
+        assert!(MetadataConstraints::new(33, 4, &[]).is_err());
+    }
+
+    #[test]
+    fn joins_base_uri_and_hash() {
+        let base = UriPrefix::new("https://arweave.net/").unwrap();
+        assert_eq!(base.join("abc_1-2.json").unwrap(), "https://arweave.net/abc_1-2.json");
+        assert!(base.join("").is_err());
+        assert!(base.join("../evil").is_err());
+        assert!(base.join("a?b").is_err());
+        assert!(base.join(&"a".repeat(MAX_URI_HASH_LEN + 1)).is_err());
+        assert!(UriPrefix::default().join("abc").is_err());
+    }
+}
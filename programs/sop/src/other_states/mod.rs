@@ -2,4 +2,10 @@ pub mod lineage_state;
 pub use lineage_state::LineageInfo;
 
 pub mod royalty_state;
-pub use royalty_state::{MintingCostDistribution, TradingPriceDistribution};
+pub use royalty_state::{LineageDistribution, MintingCostDistribution, TradingPriceDistribution};
+
+pub mod metadata_state;
+pub use metadata_state::{MetadataConstraints, UriPrefix};
+
+pub mod quota_state;
+pub use quota_state::{InvitationQuota, InvitationWindow};
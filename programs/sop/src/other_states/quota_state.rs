@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_lang::{AnchorDeserialize, AnchorSerialize};
+
+use crate::error::MyError;
+
+/// Limits on the invitations minted by `mint_activation_token`, `0` disables a limit
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Copy, PartialEq, Debug)]
+pub struct InvitationQuota {
+    /// invitations a profile can mint over its lifetime
+    pub max_per_profile: u64,
+    /// invitations the profiles of a generation can mint together
+    pub max_per_generation: u64,
+    /// length in seconds of the window `max_per_window` applies to
+    pub window: i64,
+    /// invitations a profile can mint within a window
+    pub max_per_window: u64,
+}
+
+/// Invitations a profile minted in its current window
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Copy, PartialEq, Debug)]
+pub struct InvitationWindow {
+    /// unix time the window started at
+    pub start: i64,
+    pub minted: u64,
+}
+
+impl InvitationQuota {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.window >= 0 && (self.max_per_window == 0 || self.window > 0),
+            MyError::InvalidInvitationQuota
+        );
+        Ok(())
+    }
+
+    /// checks `amount` more invitations fit, the profile having minted `profile_minted` and
+    /// its generation `generation_minted`, and counts them in the profile's `window`
+    pub fn consume(
+        &self,
+        amount: u64,
+        profile_minted: u64,
+        generation_minted: u64,
+        window: &mut InvitationWindow,
+        now: i64,
+    ) -> Result<()> {
+        let fits = |max: u64, minted: u64| max == 0 || minted.saturating_add(amount) <= max;
+        require!(
+            fits(self.max_per_profile, profile_minted)
+                && fits(self.max_per_generation, generation_minted),
+            MyError::InvitationQuotaExceeded
+        );
+        if self.window == 0 {
+            return Ok(());
+        }
+        if now >= window.start.saturating_add(self.window) {
+            *window = InvitationWindow {
+                start: now,
+                minted: 0,
+            };
+        }
+        require!(
+            fits(self.max_per_window, window.minted),
+            MyError::InvitationRateLimited
+        );
+        window.minted += amount;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enforces_lifetime_and_window_limits() {
+        let quota = InvitationQuota {
+            max_per_profile: 10,
+            max_per_generation: 100,
+            window: 60,
+            max_per_window: 3,
+        };
+        let mut window = InvitationWindow::default();
+        assert!(quota.consume(3, 0, 0, &mut window, 1_000).is_ok());
+        assert!(quota.consume(1, 3, 3, &mut window, 1_059).is_err());
+        assert!(quota.consume(3, 3, 3, &mut window, 1_060).is_ok());
+        assert_eq!(
+            window,
+            InvitationWindow {
+                start: 1_060,
+                minted: 3
+            }
+        );
+
+        assert!(quota.consume(5, 6, 6, &mut window, 2_000).is_err());
+        assert!(quota.consume(1, 0, 100, &mut window, 2_000).is_err());
+        assert!(InvitationQuota::default()
+            .consume(u64::MAX, u64::MAX, u64::MAX, &mut window, 0)
+            .is_ok());
+    }
+
+    #[test]
+    fn window_limit_needs_a_window() {
+        let quota = InvitationQuota {
+            max_per_window: 1,
+            ..Default::default()
+        };
+        assert!(quota.validate().is_err());
+        assert!(InvitationQuota {
+            window: 60,
+            ..quota
+        }
+        .validate()
+        .is_ok());
+        assert!(InvitationQuota {
+            window: -1,
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+    }
+}
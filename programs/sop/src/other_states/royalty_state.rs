@@ -1,6 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_lang::{AnchorDeserialize, AnchorSerialize};
 
+use crate::{
+    constants::{MAX_LINEAGE_DEPTH, TOTAL_SELLER_BASIS_POINTS},
+    error::MyError,
+};
+
 #[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Copy)]
 pub struct MintingCostDistribution {
     pub parent: u16,
@@ -10,6 +15,43 @@ pub struct MintingCostDistribution {
     pub genesis: u16,
 }
 
+impl MintingCostDistribution {
+    pub fn validate(&self) -> Result<()> {
+        let total = self.parent as u32
+            + self.grand_parent as u32
+            + self.great_grand_parent as u32
+            + self.ggreat_grand_parent as u32
+            + self.genesis as u32;
+        require!(
+            total == TOTAL_SELLER_BASIS_POINTS as u32,
+            MyError::InvalidMintingCostDistribution
+        );
+        Ok(())
+    }
+
+    /// Splits `cost` into `[parent, grand_parent, great_grand_parent, ggreat_grand_parent, genesis]`.
+    /// Integer-division dust goes to genesis so the payer is always charged exactly `cost`.
+    pub fn split_cost(&self, cost: u64) -> Result<[u64; 5]> {
+        self.validate()?;
+        let share = |basis_points: u16| -> u64 {
+            (cost as u128 * basis_points as u128 / TOTAL_SELLER_BASIS_POINTS as u128) as u64
+        };
+        let parent = share(self.parent);
+        let grand_parent = share(self.grand_parent);
+        let great_grand_parent = share(self.great_grand_parent);
+        let ggreat_grand_parent = share(self.ggreat_grand_parent);
+        let genesis = cost - parent - grand_parent - great_grand_parent - ggreat_grand_parent;
+
+        Ok([
+            parent,
+            grand_parent,
+            great_grand_parent,
+            ggreat_grand_parent,
+            genesis,
+        ])
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Copy)]
 pub struct TradingPriceDistribution {
     pub seller: u16,
@@ -18,3 +60,220 @@ pub struct TradingPriceDistribution {
     pub great_grand_parent: u16,
     pub genesis: u16,
 }
+
+impl TradingPriceDistribution {
+    pub fn validate(&self) -> Result<()> {
+        let total = self.seller as u32
+            + self.parent as u32
+            + self.grand_parent as u32
+            + self.great_grand_parent as u32
+            + self.genesis as u32;
+        require!(
+            total == TOTAL_SELLER_BASIS_POINTS as u32,
+            MyError::InvalidTradingPriceDistribution
+        );
+        Ok(())
+    }
+
+    /// Splits trading royalties between `[parent, grand_parent, great_grand_parent, genesis]`
+    /// in proportion to their basis points (the seller share is not part of the royalties).
+    /// Integer-division dust goes to genesis so the whole `royalties` amount is paid out.
+    pub fn split_royalties(&self, royalties: u64) -> [u64; 4] {
+        let royalty_basis_points = self.parent as u128
+            + self.grand_parent as u128
+            + self.great_grand_parent as u128
+            + self.genesis as u128;
+        if royalty_basis_points == 0 {
+            return [0, 0, 0, royalties];
+        }
+
+        let share = |basis_points: u16| -> u64 {
+            (royalties as u128 * basis_points as u128 / royalty_basis_points) as u64
+        };
+        let parent = share(self.parent);
+        let grand_parent = share(self.grand_parent);
+        let great_grand_parent = share(self.great_grand_parent);
+        let genesis = royalties
+            .saturating_sub(parent)
+            .saturating_sub(grand_parent)
+            .saturating_sub(great_grand_parent);
+
+        [parent, grand_parent, great_grand_parent, genesis]
+    }
+}
+
+/// Minting cost split over the first `lineage_depth` ancestors, `ancestors[0]` being the parent
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone, Copy)]
+pub struct LineageDistribution {
+    pub ancestors: [u16; MAX_LINEAGE_DEPTH],
+    pub genesis: u16,
+}
+
+impl LineageDistribution {
+    pub fn new(ancestors: &[u16], genesis: u16) -> Result<Self> {
+        require!(
+            ancestors.len() <= MAX_LINEAGE_DEPTH,
+            MyError::InvalidLineageDistribution
+        );
+        let mut distribution = Self {
+            genesis,
+            ..Default::default()
+        };
+        distribution.ancestors[..ancestors.len()].copy_from_slice(ancestors);
+        distribution.validate(ancestors.len())?;
+        Ok(distribution)
+    }
+
+    /// `depth` ancestors and genesis share 100%, deeper levels get nothing
+    pub fn validate(&self, depth: usize) -> Result<()> {
+        require!(
+            depth <= MAX_LINEAGE_DEPTH && self.ancestors[depth..].iter().all(|bp| *bp == 0),
+            MyError::InvalidLineageDistribution
+        );
+        let total = self.ancestors.iter().map(|bp| *bp as u32).sum::<u32>() + self.genesis as u32;
+        require!(
+            total == TOTAL_SELLER_BASIS_POINTS as u32,
+            MyError::InvalidLineageDistribution
+        );
+        Ok(())
+    }
+
+    /// Splits `cost` into the shares of the first `depth` ancestors and the genesis share.
+    /// Integer-division dust goes to genesis so the payer is always charged exactly `cost`.
+    pub fn split_cost(&self, cost: u64, depth: usize) -> Result<(Vec<u64>, u64)> {
+        self.validate(depth)?;
+        let ancestors = self.ancestors[..depth]
+            .iter()
+            .map(|bp| (cost as u128 * *bp as u128 / TOTAL_SELLER_BASIS_POINTS as u128) as u64)
+            .collect::<Vec<_>>();
+        let genesis = cost - ancestors.iter().sum::<u64>();
+        Ok((ancestors, genesis))
+    }
+}
+
+impl From<MintingCostDistribution> for LineageDistribution {
+    fn from(value: MintingCostDistribution) -> Self {
+        let mut distribution = Self {
+            genesis: value.genesis,
+            ..Default::default()
+        };
+        distribution.ancestors[..4].copy_from_slice(&[
+            value.parent,
+            value.grand_parent,
+            value.great_grand_parent,
+            value.ggreat_grand_parent,
+        ]);
+        distribution
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_distributions() {
+        let minting = MintingCostDistribution {
+            parent: 5000,
+            grand_parent: 2500,
+            great_grand_parent: 1000,
+            ggreat_grand_parent: 500,
+            genesis: 1000,
+        };
+        assert!(minting.validate().is_ok());
+        assert!(MintingCostDistribution {
+            genesis: 1001,
+            ..minting
+        }
+        .validate()
+        .is_err());
+        assert!(MintingCostDistribution {
+            parent: u16::MAX,
+            ..minting
+        }
+        .validate()
+        .is_err());
+
+        let trading = TradingPriceDistribution {
+            seller: 8000,
+            parent: 1000,
+            grand_parent: 500,
+            great_grand_parent: 250,
+            genesis: 250,
+        };
+        assert!(trading.validate().is_ok());
+        assert!(TradingPriceDistribution {
+            seller: 7999,
+            ..trading
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_split_remainder_goes_to_genesis() {
+        let minting = MintingCostDistribution {
+            parent: 3333,
+            grand_parent: 3333,
+            great_grand_parent: 3333,
+            ggreat_grand_parent: 0,
+            genesis: 1,
+        };
+        let split = minting.split_cost(100).unwrap();
+        assert_eq!(split, [33, 33, 33, 0, 1]);
+        assert_eq!(split.iter().sum::<u64>(), 100);
+
+        let split = minting.split_cost(7).unwrap();
+        assert_eq!(split, [2, 2, 2, 0, 1]);
+
+        let trading = TradingPriceDistribution {
+            seller: 8000,
+            parent: 1000,
+            grand_parent: 500,
+            great_grand_parent: 250,
+            genesis: 250,
+        };
+        let split = trading.split_royalties(101);
+        assert_eq!(split, [50, 25, 12, 14]);
+        assert_eq!(split.iter().sum::<u64>(), 101);
+    }
+
+    #[test]
+    fn test_split_unvalidated_distribution() {
+        let minting = MintingCostDistribution {
+            parent: 10000,
+            grand_parent: 10000,
+            great_grand_parent: 0,
+            ggreat_grand_parent: 0,
+            genesis: 0,
+        };
+        assert!(minting.split_cost(100).is_err());
+
+        let lineage = LineageDistribution {
+            ancestors: [u16::MAX; MAX_LINEAGE_DEPTH],
+            genesis: 0,
+        };
+        assert!(lineage.split_cost(u64::MAX, MAX_LINEAGE_DEPTH).is_err());
+
+        // shares configured beyond the lineage depth would never be paid
+        let lineage = LineageDistribution::new(&[5000, 5000], 0).unwrap();
+        assert!(lineage.split_cost(100, 2).is_ok());
+        assert!(lineage.split_cost(100, 1).is_err());
+    }
+
+    #[test]
+    fn test_lineage_distribution() {
+        assert!(LineageDistribution::new(&[5000, 2000], 2999).is_err());
+        assert!(LineageDistribution::new(&[1000; MAX_LINEAGE_DEPTH + 1], 0).is_err());
+
+        let lineage = LineageDistribution::new(&[3333, 3333, 3333], 1).unwrap();
+        let (ancestors, genesis) = lineage.split_cost(100, 3).unwrap();
+        assert_eq!(ancestors, vec![33, 33, 33]);
+        assert_eq!(genesis, 1);
+
+        let genesis_only = LineageDistribution::new(&[], 10_000).unwrap();
+        let (ancestors, genesis) = genesis_only.split_cost(100, 0).unwrap();
+        assert!(ancestors.is_empty());
+        assert_eq!(genesis, 100);
+    }
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CHILD_INDEX_PAGE_SIZE, SEED_CHILD_INDEX};
+
+/// One page of the children of `parent`, at `[SEED_CHILD_INDEX, parent, page]`.
+/// Child `n` of a parent lives in page `n / CHILD_INDEX_PAGE_SIZE`, slot `n % CHILD_INDEX_PAGE_SIZE`.
+/// Children minted before the index existed leave their slots empty.
+#[account]
+pub struct ChildIndexState {
+    pub parent: Pubkey,
+    pub page: u64,
+    pub _bump: u8,
+    pub children: [Pubkey; CHILD_INDEX_PAGE_SIZE],
+}
+
+impl ChildIndexState {
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
+
+    /// page holding child number `child`, counting from 0
+    pub fn page_of(child: u64) -> u64 {
+        child / CHILD_INDEX_PAGE_SIZE as u64
+    }
+
+    pub fn address(parent: &Pubkey, page: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[SEED_CHILD_INDEX, parent.as_ref(), &page.to_le_bytes()],
+            &crate::ID,
+        )
+        .0
+    }
+
+    /// stores child number `child` of `parent`, setting the page up on first use
+    pub fn push(&mut self, parent: Pubkey, child: u64, mint: Pubkey, bump: u8) {
+        self.parent = parent;
+        self.page = Self::page_of(child);
+        self._bump = bump;
+        self.children[child as usize % CHILD_INDEX_PAGE_SIZE] = mint;
+    }
+
+    /// indexed children in this page, in minting order
+    pub fn children(&self) -> impl Iterator<Item = Pubkey> + '_ {
+        self.children
+            .iter()
+            .copied()
+            .filter(|child| *child != Pubkey::default())
+    }
+}
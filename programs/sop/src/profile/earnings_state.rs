@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{MAX_LINEAGE_DEPTH, MAX_PAYMENT_MINTS},
+    error::MyError,
+};
+
+/// OPOS owed to whoever holds `profile`, kept in the main state vault until claimed.
+/// Lives at `get_vault_pda(profile)`, the address the profile royalties are paid to.
+/// Also keeps the lifetime earnings of the profile, whoever held it at the time.
+#[account]
+pub struct EarningsState {
+    pub profile: Pubkey,
+    pub unclaimed: u64,
+    pub _bump: u8,
+    /// minting cost earned as `ancestors[i]` of the minted profile
+    pub minting: [u64; MAX_LINEAGE_DEPTH],
+    /// minting cost earned as genesis
+    pub minting_as_genesis: u64,
+    /// invitation price earned by the inviter and its lineage
+    pub invitations: u64,
+    /// curve royalties earned on OPOS trades of descendant profiles
+    pub trading_royalties: u64,
+    pub claimed: u64,
+    /// set by `retire_profile`, minting cost credited to a retired ancestor goes to genesis
+    pub retired: bool,
+    /// minting cost paid in `PaymentState.mints[i]`, escrowed in that mint
+    pub payment_unclaimed: [u64; MAX_PAYMENT_MINTS],
+    pub payment_claimed: [u64; MAX_PAYMENT_MINTS],
+}
+
+#[derive(Clone, Copy)]
+pub enum EarningsSource {
+    /// ancestor level, `0` being the parent
+    Minting(usize),
+    MintingAsGenesis,
+    Invitation,
+    TradingRoyalty,
+}
+
+impl EarningsState {
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
+
+    /// adds `amount` to the lifetime stats, escrowed minting cost also becomes claimable
+    pub fn record(&mut self, source: EarningsSource, amount: u64) {
+        match source {
+            EarningsSource::Minting(level) => {
+                self.minting[level] += amount;
+                self.unclaimed += amount;
+            }
+            EarningsSource::MintingAsGenesis => {
+                self.minting_as_genesis += amount;
+                self.unclaimed += amount;
+            }
+            EarningsSource::Invitation => self.invitations += amount,
+            EarningsSource::TradingRoyalty => self.trading_royalties += amount,
+        }
+    }
+
+    /// escrowed minting cost paid in `PaymentState.mints[index]`, the lifetime stats only
+    /// count OPOS
+    pub fn record_payment(&mut self, index: usize, amount: u64) {
+        self.payment_unclaimed[index] += amount;
+    }
+
+    pub fn has_payment_unclaimed(&self) -> bool {
+        self.payment_unclaimed.iter().any(|amount| *amount > 0)
+    }
+}
+
+/// Records `payouts` (profile, source, amount) on the earnings accounts of the paid profiles.
+/// `accounts` may repeat a profile, each earnings account is only loaded and written once.
+pub fn record_earnings<'info>(
+    accounts: &[AccountInfo<'info>],
+    payouts: &[(Pubkey, EarningsSource, u64)],
+) -> Result<()> {
+    let mut recorded = 0;
+    for (index, account) in accounts.iter().enumerate() {
+        if accounts[..index].iter().any(|seen| seen.key() == account.key()) {
+            continue;
+        }
+        let mut earnings = Account::<EarningsState>::try_from(account)?;
+        for (profile, source, amount) in payouts {
+            if *profile == earnings.profile {
+                earnings.record(*source, *amount);
+                recorded += 1;
+            }
+        }
+        earnings.exit(&crate::ID)?;
+    }
+    require!(recorded == payouts.len(), MyError::InvalidLineageAccounts);
+    Ok(())
+}
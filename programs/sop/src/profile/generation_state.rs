@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+/// Invitations minted by the profiles of `generation`, counted since invitation quotas exist
+#[account]
+pub struct GenerationState {
+    pub generation: u64,
+    pub total_invitations: u64,
+    pub _bump: u8,
+}
+
+impl GenerationState {
+    pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
+}
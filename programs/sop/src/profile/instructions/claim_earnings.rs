@@ -0,0 +1,222 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{
+    _main::{MainState, PaymentState},
+    constants::{
+        PAUSE_PROFILES, SEED_MAIN_STATE, SEED_PAYMENT_STATE, SEED_PROFILE_STATE, SEED_VAULT,
+    },
+    error::MyError,
+    profile::{EarningsState, ProfileState},
+    utils::{transfer_sol_from_main, transfer_tokens_from_main},
+};
+
+/// Creates the earnings account of a profile minted before earnings were escrowed
+pub fn init_earnings(ctx: Context<AInitEarnings>) -> Result<()> {
+    let earnings = &mut ctx.accounts.earnings;
+    earnings.profile = ctx.accounts.profile.key();
+    earnings._bump = *ctx.bumps.get("earnings").unwrap();
+    Ok(())
+}
+
+/// Pays everything credited to `profile` out to its current holder
+pub fn claim_earnings(ctx: Context<AClaimEarnings>) -> Result<()> {
+    let earnings = &mut ctx.accounts.earnings;
+    let amount = earnings.unclaimed;
+    require!(amount > 0, MyError::NothingToClaim);
+    earnings.unclaimed = 0;
+    earnings.claimed += amount;
+
+    transfer_tokens_from_main(
+        ctx.accounts.opos_vault.to_account_info(),
+        ctx.accounts.holder_opos_ata.to_account_info(),
+        &ctx.accounts.main_state,
+        ctx.accounts.token_program.to_account_info(),
+        amount,
+    )?;
+    Ok(())
+}
+
+/// Pays the minting cost credited to `profile` in `PaymentState.mints[index]` out to its
+/// current holder. Native SOL comes out of the main state account.
+pub fn claim_payment_earnings(ctx: Context<AClaimPaymentEarnings>, index: u8) -> Result<()> {
+    let index = index as usize;
+    let payment = *ctx
+        .accounts
+        .payment_state
+        .mints
+        .get(index)
+        .ok_or(MyError::PaymentMintNotAccepted)?;
+    require_keys_eq!(
+        payment.mint,
+        ctx.accounts.payment_mint.key(),
+        MyError::InvalidPaymentAccounts
+    );
+
+    let earnings = &mut ctx.accounts.earnings;
+    let amount = earnings.payment_unclaimed[index];
+    require!(amount > 0, MyError::NothingToClaim);
+    earnings.payment_unclaimed[index] = 0;
+    earnings.payment_claimed[index] += amount;
+
+    if payment.is_native() {
+        let main_state = &mut ctx.accounts.main_state;
+        main_state.escrowed_sol = main_state.escrowed_sol.saturating_sub(amount);
+        return transfer_sol_from_main(main_state, ctx.accounts.holder.to_account_info(), amount);
+    }
+    let main_state = &ctx.accounts.main_state;
+    match (
+        &ctx.accounts.payment_vault,
+        &ctx.accounts.holder_payment_ata,
+    ) {
+        (Some(payment_vault), Some(holder_payment_ata)) => transfer_tokens_from_main(
+            payment_vault.to_account_info(),
+            holder_payment_ata.to_account_info(),
+            main_state,
+            ctx.accounts.token_program.to_account_info(),
+            amount,
+        ),
+        _ => err!(MyError::InvalidPaymentAccounts),
+    }
+}
+
+#[derive(Accounts)]
+pub struct AInitEarnings<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    ///CHECK: has to have a profile state
+    pub profile: AccountInfo<'info>,
+
+    #[account(
+        seeds = [SEED_PROFILE_STATE, profile.key().as_ref()],
+        bump,
+    )]
+    pub profile_state: Box<Account<'info, ProfileState>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [SEED_VAULT, profile.key().as_ref()],
+        bump,
+        space = 8 + EarningsState::MAX_SIZE,
+    )]
+    pub earnings: Box<Account<'info, EarningsState>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AClaimEarnings<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    ///CHECK: only used as the profile state and earnings seed
+    pub profile: AccountInfo<'info>,
+
+    #[account(
+        seeds = [SEED_PROFILE_STATE, profile.key().as_ref()],
+        bump,
+        constraint = profile_state.current_holder == holder.key() @ MyError::OnlyProfileHolderAllow,
+    )]
+    pub profile_state: Box<Account<'info, ProfileState>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_VAULT, profile.key().as_ref()],
+        bump = earnings._bump,
+    )]
+    pub earnings: Box<Account<'info, EarningsState>>,
+
+    #[account(
+        seeds = [SEED_MAIN_STATE],
+        bump,
+        constraint = !main_state.is_paused(PAUSE_PROFILES) @ MyError::ProfilesPaused,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    ///CHECK:
+    #[account(address = main_state.opos_token)]
+    pub opos_token: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = opos_token,
+        associated_token::authority = main_state,
+    )]
+    pub opos_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = holder,
+        associated_token::mint = opos_token,
+        associated_token::authority = holder,
+    )]
+    pub holder_opos_ata: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AClaimPaymentEarnings<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    ///CHECK: only used as the profile state and earnings seed
+    pub profile: AccountInfo<'info>,
+
+    #[account(
+        seeds = [SEED_PROFILE_STATE, profile.key().as_ref()],
+        bump,
+        constraint = profile_state.current_holder == holder.key() @ MyError::OnlyProfileHolderAllow,
+    )]
+    pub profile_state: Box<Account<'info, ProfileState>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_VAULT, profile.key().as_ref()],
+        bump = earnings._bump,
+    )]
+    pub earnings: Box<Account<'info, EarningsState>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_MAIN_STATE],
+        bump,
+        constraint = !main_state.is_paused(PAUSE_PROFILES) @ MyError::ProfilesPaused,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        seeds = [SEED_PAYMENT_STATE],
+        bump = payment_state._bump,
+    )]
+    pub payment_state: Box<Account<'info, PaymentState>>,
+
+    pub payment_mint: Box<Account<'info, Mint>>,
+
+    // not needed for native SOL
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = main_state,
+    )]
+    pub payment_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        init_if_needed,
+        payer = holder,
+        associated_token::mint = payment_mint,
+        associated_token::authority = holder,
+    )]
+    pub holder_payment_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,338 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, MintTo, Token, TokenAccount},
+};
+use mpl_token_metadata::{
+    instruction::{builders::Create, InstructionBuilder},
+    state::{AssetData, EDITION, PREFIX as METADATA},
+    ID as MPL_ID,
+};
+use solana_program::program::invoke_signed;
+
+use crate::{
+    _main::MainState,
+    constants::{
+        PAUSE_PROFILES, SEED_CHILD_INDEX, SEED_MAIN_STATE, SEED_PROFILE_STATE, SEED_VAULT,
+    },
+    error::MyError,
+    other_states::LineageInfo,
+    profile::{profile_creators, ChildIndexState, EarningsState, ProfileState},
+    utils::{lock_profile, verify_collection_item_by_main},
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ImportProfileInput {
+    pub name: String,
+    pub symbol: String,
+    pub uri_hash: String,
+    pub lineage: LineageInfo,
+}
+
+/// Mints a profile to `receiver` with the lineage it had in a previous deployment or an
+/// off-chain sign-up. The lineage has to match `parent_profile_state`, so parents are
+/// imported first. No minting cost is charged.
+///
+/// The receiver signs so their profile account is frozen under the main state right away,
+/// like a minted profile it then only moves through `transfer_profile`.
+pub fn import_profile(ctx: Context<AImportProfile>, input: ImportProfileInput) -> Result<()> {
+    {
+        let main_state = &mut ctx.accounts.main_state;
+        let profile_state = &mut ctx.accounts.profile_state;
+        let parent_profile_state = &mut ctx.accounts.parent_profile_state;
+        require!(
+            input
+                .lineage
+                .is_child_of(parent_profile_state.mint, &parent_profile_state.lineage),
+            MyError::InvalidImportLineage
+        );
+
+        profile_state.mint = ctx.accounts.profile.key();
+        profile_state.version = ProfileState::VERSION;
+        profile_state.lineage = input.lineage;
+        profile_state.current_holder = ctx.accounts.receiver.key();
+        profile_state.ancestors = parent_profile_state.child_ancestors(main_state.genesis_profile);
+
+        let profile_earnings = &mut ctx.accounts.profile_earnings;
+        profile_earnings.profile = ctx.accounts.profile.key();
+        profile_earnings._bump = *ctx.bumps.get("profile_earnings").unwrap();
+
+        ctx.accounts.child_index.push(
+            parent_profile_state.mint,
+            parent_profile_state.lineage.total_child,
+            ctx.accounts.profile.key(),
+            *ctx.bumps.get("child_index").unwrap(),
+        );
+        parent_profile_state.lineage.total_child += 1;
+        main_state.total_minted_profile += 1;
+    }
+    {
+        ctx.accounts.mint(input.name, input.symbol, input.uri_hash)?;
+    }
+    {
+        ctx.accounts.verify_collection_item()?;
+    }
+    {
+        let accounts = &ctx.accounts;
+        lock_profile(
+            accounts.receiver_profile_ata.to_account_info(),
+            accounts.receiver.to_account_info(),
+            accounts.profile.to_account_info(),
+            accounts.profile_edition.to_account_info(),
+            &accounts.main_state,
+            accounts.mpl_program.to_account_info(),
+            accounts.token_program.to_account_info(),
+        )?;
+    }
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AImportProfile<'info> {
+    #[account(mut, address = main_state.owner @ MyError::OnlyOwnerCanCall)]
+    pub admin: Signer<'info>,
+
+    /// wallet of the imported member, signs to have the profile locked
+    pub receiver: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_MAIN_STATE],
+        bump,
+        constraint = !main_state.is_paused(PAUSE_PROFILES) @ MyError::ProfilesPaused,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        init,
+        signer,
+        payer = admin,
+        mint::decimals = 0,
+        mint::authority = main_state,
+        mint::freeze_authority = main_state,
+    )]
+    pub profile: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = admin,
+        associated_token::mint = profile,
+        associated_token::authority = receiver,
+    )]
+    pub receiver_profile_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [SEED_PROFILE_STATE, profile.key().as_ref()],
+        bump,
+        space = 8 + ProfileState::MAX_SIZE
+    )]
+    pub profile_state: Box<Account<'info, ProfileState>>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [SEED_VAULT, profile.key().as_ref()],
+        bump,
+        space = 8 + EarningsState::MAX_SIZE,
+    )]
+    pub profile_earnings: Box<Account<'info, EarningsState>>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        seeds=[
+            METADATA.as_ref(),
+            MPL_ID.as_ref(),
+            profile.key().as_ref(),
+        ],
+        bump,
+        seeds::program = MPL_ID
+    )]
+    pub profile_metadata: AccountInfo<'info>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        seeds=[
+            METADATA.as_ref(),
+            MPL_ID.as_ref(),
+            profile.key().as_ref(),
+            EDITION.as_ref(),
+        ],
+        bump,
+        seeds::program = MPL_ID
+    )]
+    pub profile_edition: AccountInfo<'info>,
+
+    ///CHECK: only used as the parent profile state seed
+    pub parent_profile: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROFILE_STATE, parent_profile.key().as_ref()],
+        bump,
+    )]
+    pub parent_profile_state: Box<Account<'info, ProfileState>>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [
+            SEED_CHILD_INDEX,
+            parent_profile.key().as_ref(),
+            &ChildIndexState::page_of(parent_profile_state.lineage.total_child).to_le_bytes(),
+        ],
+        bump,
+        space = 8 + ChildIndexState::MAX_SIZE,
+    )]
+    pub child_index: Box<Account<'info, ChildIndexState>>,
+
+    ///CHECK:
+    #[account(mut, address = main_state.profile_collection)]
+    pub collection: AccountInfo<'info>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        seeds=[
+            METADATA.as_ref(),
+            MPL_ID.as_ref(),
+            collection.key().as_ref(),
+        ],
+        bump,
+        seeds::program = MPL_ID
+    )]
+    pub collection_metadata: AccountInfo<'info>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        seeds=[
+            METADATA.as_ref(),
+            MPL_ID.as_ref(),
+            collection.key().as_ref(),
+            EDITION.as_ref(),
+        ],
+        bump,
+        seeds::program = MPL_ID
+    )]
+    pub collection_edition: AccountInfo<'info>,
+
+    ///CHECK:
+    #[account()]
+    pub sysvar_instructions: AccountInfo<'info>,
+
+    ///CHECK:
+    #[account(address = MPL_ID)]
+    pub mpl_program: AccountInfo<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AImportProfile<'info> {
+    pub fn mint(&mut self, name: String, symbol: String, uri_hash: String) -> Result<()> {
+        let mint = self.profile.to_account_info();
+        let admin = self.admin.to_account_info();
+        let system_program = self.system_program.to_account_info();
+        let token_program = self.token_program.to_account_info();
+        let mpl_program = self.mpl_program.to_account_info();
+        let metadata = self.profile_metadata.to_account_info();
+        let edition = self.profile_edition.to_account_info();
+        let sysvar_instructions = self.sysvar_instructions.to_account_info();
+        let main_state = &self.main_state;
+        let uri = main_state.profile_uri(&uri_hash)?;
+
+        let cpi_accounts = MintTo {
+            mint: mint.clone(),
+            to: self.receiver_profile_ata.to_account_info(),
+            authority: main_state.to_account_info(),
+        };
+        token::mint_to(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                cpi_accounts,
+                &[&[SEED_MAIN_STATE, &[main_state._bump]]],
+            ),
+            1,
+        )?;
+
+        let (creators, seller_fee_basis_points) = profile_creators(
+            self.profile_state.lineage.creator,
+            &self.profile_state.lineage,
+            main_state,
+        );
+
+        let asset_data = AssetData {
+            name,
+            symbol,
+            uri,
+            collection: Some(mpl_token_metadata::state::Collection {
+                verified: false,
+                key: self.collection.key(),
+            }),
+            uses: None,
+            creators: Some(creators),
+            collection_details: Some(mpl_token_metadata::state::CollectionDetails::V1 { size: 0 }),
+            is_mutable: true,
+            rule_set: None,
+            token_standard: mpl_token_metadata::state::TokenStandard::NonFungible,
+            primary_sale_happened: true,
+            seller_fee_basis_points,
+        };
+
+        let ix = Create {
+            mint: mint.key(),
+            payer: admin.key(),
+            authority: main_state.key(),
+            initialize_mint: false,
+            system_program: system_program.key(),
+            metadata: metadata.key(),
+            update_authority: main_state.key(),
+            spl_token_program: token_program.key(),
+            sysvar_instructions: sysvar_instructions.key(),
+            update_authority_as_signer: true,
+            master_edition: Some(edition.key()),
+            args: mpl_token_metadata::instruction::CreateArgs::V1 {
+                asset_data,
+                decimals: Some(0),
+                print_supply: Some(mpl_token_metadata::state::PrintSupply::Zero),
+            },
+        }
+        .instruction();
+
+        invoke_signed(
+            &ix,
+            &[
+                mint,
+                admin,
+                main_state.to_account_info(),
+                metadata,
+                edition,
+                mpl_program,
+                token_program,
+                system_program,
+                sysvar_instructions,
+            ],
+            &[&[SEED_MAIN_STATE, &[main_state._bump]]],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn verify_collection_item(&mut self) -> Result<()> {
+        verify_collection_item_by_main(
+            self.profile_metadata.to_account_info(),
+            self.collection.to_account_info(),
+            self.collection_metadata.to_account_info(),
+            self.collection_edition.to_account_info(),
+            &self.main_state,
+            self.mpl_program.to_account_info(),
+            self.system_program.to_account_info(),
+            self.sysvar_instructions.to_account_info(),
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{
+    _main::main_state::MainState,
+    constants::{SEED_MAIN_STATE, SEED_PROFILE_STATE},
+    error::MyError,
+    profile::profile_state::ProfileState,
+    utils::{migrate_account, resolve_profile_holder},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+/// Grows an existing `ProfileState` to the current layout and stamps `ProfileState::VERSION`.
+/// `holder_ata` backfills `current_holder` of profiles minted before it existed.
+pub fn migrate_profile_state(ctx: Context<AMigrateProfileState>) -> Result<()> {
+    let genesis_profile = ctx.accounts.main_state.genesis_profile;
+    let holder = match &ctx.accounts.holder_ata {
+        Some(holder_ata) => Some(resolve_profile_holder(holder_ata, ctx.accounts.profile.key)?),
+        None => None,
+    };
+    migrate_account::<ProfileState>(
+        ctx.accounts.profile_state.to_account_info(),
+        ctx.accounts.owner.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        8 + ProfileState::MAX_SIZE,
+        |state| {
+            state.backfill_ancestors(genesis_profile);
+            if let Some(holder) = holder {
+                state.current_holder = holder;
+            }
+            state.version = ProfileState::VERSION;
+        },
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AMigrateProfileState<'info> {
+    #[account(mut, address = main_state.owner @ MyError::OnlyOwnerCanCall)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_MAIN_STATE],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    ///CHECK: only used as the profile state seed
+    pub profile: AccountInfo<'info>,
+
+    ///CHECK: deserialized in `migrate_profile_state` once it has the current size
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [SEED_PROFILE_STATE, profile.key().as_ref()],
+        bump,
+    )]
+    pub profile_state: AccountInfo<'info>,
+
+    pub holder_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub system_program: Program<'info, System>,
+}
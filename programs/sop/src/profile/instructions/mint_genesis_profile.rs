@@ -20,20 +20,22 @@ use solana_program::program::{invoke, invoke_signed};
 use crate::{
     _main::MainState,
     collection_factory::collection_state::CollectionState,
-    constants::{SEED_COLLECTION_STATE, SEED_MAIN_STATE, SEED_PROFILE_STATE},
+    constants::{
+        MAX_LINEAGE_DEPTH, SEED_COLLECTION_STATE, SEED_MAIN_STATE, SEED_PROFILE_STATE, SEED_VAULT,
+    },
     error::MyError,
     other_states::LineageInfo,
-    profile::ProfileState,
-    utils::{_verify_collection, verify_collection_item_by_main},
+    profile::{EarningsState, ProfileState},
+    utils::{_verify_collection, lock_profile, verify_collection_item_by_main},
 };
 
 #[derive(AnchorSerialize, AnchorDeserialize, Default, Clone)]
 pub struct MintProfileByAdminInput {
-    name: String,
-    symbol: String,
-    uri: String,
-    lineage: LineageInfo,
-    parent_mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub lineage: LineageInfo,
+    pub parent_mint: Pubkey,
 }
 
 pub fn mint_genesis_profile(
@@ -55,11 +57,18 @@ pub fn mint_genesis_profile(
         //state changes
         let profile = ctx.accounts.profile.key();
         profile_state.mint = profile;
+        profile_state.version = ProfileState::VERSION;
         profile_state.lineage.creator = ctx.accounts.admin.key();
+        profile_state.current_holder = ctx.accounts.admin.key();
         profile_state.lineage.parent = profile;
         profile_state.lineage.grand_parent = profile;
         profile_state.lineage.great_grand_parent = profile;
         profile_state.lineage.ggreat_grand_parent = profile;
+        profile_state.ancestors = [profile; MAX_LINEAGE_DEPTH];
+
+        let profile_earnings = &mut ctx.accounts.profile_earnings;
+        profile_earnings.profile = profile;
+        profile_earnings._bump = *ctx.bumps.get("profile_earnings").unwrap();
 
         // profile_state.mint = ctx.accounts.profile.key();
         // profile_state.lineage.creator = ctx.accounts.admin.key();
@@ -129,6 +138,15 @@ pub struct AMintProfileByAdmin<'info> {
     )]
     pub profile_state: Box<Account<'info, ProfileState>>,
 
+    #[account(
+        init,
+        payer = admin,
+        seeds = [SEED_VAULT, profile.key().as_ref()],
+        bump,
+        space = 8 + EarningsState::MAX_SIZE,
+    )]
+    pub profile_earnings: Box<Account<'info, EarningsState>>,
+
     ///CHECK:
     #[account(
         mut,
@@ -321,6 +339,16 @@ impl<'info> AMintProfileByAdmin<'info> {
             &[&[SEED_MAIN_STATE, &[main_state._bump]]],
         )?;
 
+        lock_profile(
+            admin_ata,
+            admin,
+            mint,
+            edition,
+            main_state,
+            mpl_program,
+            token_program,
+        )?;
+
         Ok(())
     }
 
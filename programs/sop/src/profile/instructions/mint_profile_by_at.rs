@@ -16,25 +16,24 @@ use mpl_token_metadata::{
     },
     ID as MPL_ID,
 };
-use solana_address_lookup_table_program::{
-    instruction::{create_lookup_table, extend_lookup_table, freeze_lookup_table},
-    ID as ADDRESS_LOOKUP_TABLE_PROGRAM,
-};
 use solana_program::program::{invoke, invoke_signed};
 
 use crate::{
-    _main::MainState,
-    activation_token::ActivationTokenState,
+    _main::{MainState, PaymentMint, PaymentState, PricingState},
+    activation_token::{
+        ActivationTokenIssueState, ActivationTokenReceiptState, ActivationTokenState,
+    },
     constants::{
-        SEED_ACTIVATION_TOKEN_STATE, SEED_MAIN_STATE, SEED_PROFILE_STATE, SEED_VAULT,
-        TOTAL_SELLER_BASIS_POINTS,
+        PAUSE_PROFILES, SEED_ACTIVATION_TOKEN_ISSUE, SEED_ACTIVATION_TOKEN_RECEIPT,
+        SEED_ACTIVATION_TOKEN_STATE, SEED_CHILD_INDEX, SEED_MAIN_STATE, SEED_PAYMENT_STATE,
+        SEED_PRICING_STATE, SEED_PROFILE_STATE, SEED_VAULT, TOTAL_SELLER_BASIS_POINTS,
     },
+    curve::{CurveV0, TokenBondingV0},
     error::MyError,
     other_states::LineageInfo,
-    profile_state::ProfileState,
+    profile::{ChildIndexState, EarningsSource, EarningsState, ProfileState},
     utils::{
-        get_vault_pda, init_ata_if_needed, transfer_tokens,
-        verify_collection_item_by_main,
+        get_vault_pda, lock_profile, transfer_sol, transfer_tokens, verify_collection_item_by_main,
     },
 };
 
@@ -47,11 +46,17 @@ pub struct MintProfileByAtInput {
 }
 
 ///MINT FakeID by activation_token
-pub fn mint_profile_by_at(
-    ctx: Context<AMintProfileByAt>,
+/// `remaining_accounts` holds the earnings account of each of the first
+/// `main_state.lineage_depth` ancestors of the new profile, parent first.
+/// The minting cost is paid in OPOS, or in `payment_mint` when one is passed, and the
+/// lineage is credited in the mint paid with. `max_payment` caps the amount of
+/// `payment_mint` charged, it is required for curve priced mints.
+pub fn mint_profile_by_at<'info>(
+    ctx: Context<'_, '_, '_, 'info, AMintProfileByAt<'info>>,
     name: Box<String>,
     symbol: Box<String>,
     uri_hash: Box<String>,
+    max_payment: Option<u64>,
 ) -> Result<()> {
     let name = *name;
     let symbol = *symbol;
@@ -67,17 +72,31 @@ pub fn mint_profile_by_at(
 
         //state changes
         profile_state.mint = ctx.accounts.profile.key();
+        profile_state.version = ProfileState::VERSION;
         profile_state.lineage.creator = ctx.accounts.user.key();
+        profile_state.current_holder = ctx.accounts.user.key();
         profile_state.lineage.parent = parent_profile_state.mint;
         profile_state.lineage.grand_parent = parent_profile_state.lineage.parent;
         profile_state.lineage.great_grand_parent = parent_profile_state.lineage.grand_parent;
         profile_state.lineage.ggreat_grand_parent = parent_profile_state.lineage.great_grand_parent;
         profile_state.lineage.generation = parent_profile_state.lineage.generation + 1;
+        profile_state.ancestors = parent_profile_state.child_ancestors(main_state.genesis_profile);
+
+        let profile_earnings = &mut ctx.accounts.profile_earnings;
+        profile_earnings.profile = ctx.accounts.profile.key();
+        profile_earnings._bump = *ctx.bumps.get("profile_earnings").unwrap();
+        ctx.accounts.child_index.push(
+            parent_profile_state.mint,
+            parent_profile_state.lineage.total_child,
+            ctx.accounts.profile.key(),
+            *ctx.bumps.get("child_index").unwrap(),
+        );
         parent_profile_state.lineage.total_child += 1;
     }
     {
         //NOTE: minting
-        ctx.accounts.mint(name, symbol, uri_hash)?;
+        ctx.accounts
+            .mint(name, symbol, uri_hash, max_payment, ctx.remaining_accounts)?;
     }
     {
         //NOTE: created mint collection verifiaction
@@ -92,6 +111,64 @@ pub fn mint_profile_by_at(
 
 
 
+/// Metadata creators of a profile, the lineage vaults sharing the trading royalties
+pub fn profile_creators(
+    creator: Pubkey,
+    lineage: &LineageInfo,
+    main_state: &MainState,
+) -> (Vec<Creator>, u16) {
+    let trading_price_distribution = main_state.trading_price_distribution;
+    let seller_fee_basis_points = TOTAL_SELLER_BASIS_POINTS - trading_price_distribution.seller;
+    let creators = vec![
+        //NOTE: currently not royalty info for creator
+        Creator {
+            address: creator,
+            verified: false,
+            share: 0,
+        },
+        Creator {
+            address: get_vault_pda(&lineage.parent).0,
+            verified: false,
+            share: (trading_price_distribution.parent as u64 * 100u64
+                / seller_fee_basis_points as u64) as u8,
+        },
+        Creator {
+            address: get_vault_pda(&lineage.grand_parent).0,
+            verified: false,
+            share: (trading_price_distribution.grand_parent as u64 * 100u64
+                / seller_fee_basis_points as u64) as u8,
+        },
+        Creator {
+            address: get_vault_pda(&lineage.great_grand_parent).0,
+            verified: false,
+            share: (trading_price_distribution.great_grand_parent as u64 * 100u64
+                / seller_fee_basis_points as u64) as u8,
+        },
+        Creator {
+            address: get_vault_pda(&main_state.genesis_profile).0,
+            verified: false,
+            share: (trading_price_distribution.genesis as u64 * 100u64
+                / seller_fee_basis_points as u64) as u8,
+        },
+    ];
+
+    let mut unique_creators = HashMap::<Pubkey, Creator>::new();
+    for creator in creators.into_iter() {
+        let res = unique_creators.get_mut(&creator.address);
+        if let Some(value) = res {
+            value.share += creator.share;
+        } else {
+            unique_creators.insert(creator.address, creator);
+        }
+    }
+
+    let creators = unique_creators
+        .into_iter()
+        .map(|(k, v)| v)
+        .collect::<Vec<_>>();
+    (creators, seller_fee_basis_points)
+}
+
 #[derive(Accounts)]
 #[instruction(
     name: Box<String>,
@@ -122,24 +199,61 @@ pub struct AMintProfileByAt<'info> {
     )]
     pub user_activation_token_ata: Box<Account<'info, TokenAccount>>,
 
+    // Issue the burned invitation is redeemed against
+    #[account(
+        mut,
+        seeds = [
+            SEED_ACTIVATION_TOKEN_ISSUE,
+            activation_token.key().as_ref(),
+            &activation_token_issue.index.to_le_bytes(),
+        ],
+        bump = activation_token_issue._bump,
+    )]
+    pub activation_token_issue: Box<Account<'info, ActivationTokenIssueState>>,
+
+    // The user's invitations of that issue
+    #[account(
+        mut,
+        seeds = [
+            SEED_ACTIVATION_TOKEN_RECEIPT,
+            activation_token_issue.key().as_ref(),
+            user.key().as_ref(),
+        ],
+        bump = activation_token_receipt._bump,
+    )]
+    pub activation_token_receipt: Box<Account<'info, ActivationTokenReceiptState>>,
+
     #[account(
         mut,
         seeds = [SEED_MAIN_STATE],
         bump,
+        constraint = !main_state.is_paused(PAUSE_PROFILES) @ MyError::ProfilesPaused,
     )]
     pub main_state: Box<Account<'info, MainState>>,
 
+    #[account(
+        seeds = [SEED_PRICING_STATE],
+        bump = pricing_state._bump,
+    )]
+    pub pricing_state: Box<Account<'info, PricingState>>,
+
     #[account(mut)]
     pub activation_token: Box<Account<'info, Mint>>,
 
+    #[account(
+        seeds = [SEED_ACTIVATION_TOKEN_STATE, activation_token.key().as_ref()],
+        bump,
+    )]
+    pub activation_token_state: Box<Account<'info, ActivationTokenState>>,
+
     ///CHECK:
     #[account(
         init,
         signer,
         payer = user,
         mint::decimals = 0,
-        mint::authority = user,
-        mint::freeze_authority = user,
+        mint::authority = main_state,
+        mint::freeze_authority = main_state,
     )]
     pub profile: Box<Account<'info, Mint>>,
 
@@ -160,6 +274,15 @@ pub struct AMintProfileByAt<'info> {
     )]
     pub profile_state: Box<Account<'info, ProfileState>>,
 
+    #[account(
+        init,
+        payer = user,
+        seeds = [SEED_VAULT, profile.key().as_ref()],
+        bump,
+        space = 8 + EarningsState::MAX_SIZE,
+    )]
+    pub profile_earnings: Box<Account<'info, EarningsState>>,
+
     ///CHECK:
     #[account(
         mut,
@@ -206,8 +329,8 @@ pub struct AMintProfileByAt<'info> {
     )]
     pub parent_profile_state: Box<Account<'info, ProfileState>>,
 
-    ///CHECK: //PERF:
-    #[account(mut)]
+    ///CHECK:
+    #[account(mut, address = main_state.profile_collection @ MyError::UnknownNft)]
     pub collection: AccountInfo<'info>,
 
     ///CHECK:
@@ -237,126 +360,97 @@ pub struct AMintProfileByAt<'info> {
     )]
     pub collection_edition: AccountInfo<'info>,
 
-    ///CHECK:
-    // #[account(address = ADDRESS_LOOKUP_TABLE_PROGRAM)]
-    // pub address_lookup_table_program: AccountInfo<'info>,
-
     ///CHECK:
     #[account()]
     pub sysvar_instructions: AccountInfo<'info>,
 
     //NOTE: profile minting cost distribution account
-    // #[account(address = activation_token_state.parent_profile @ MyError::ProfileIdMissMatch)]
+    #[account(address = activation_token_state.parent_profile @ MyError::ProfileIdMissMatch)]
     pub parent_profile: Box<Account<'info, Mint>>,
 
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [
+            SEED_CHILD_INDEX,
+            parent_profile.key().as_ref(),
+            &ChildIndexState::page_of(parent_profile_state.lineage.total_child).to_le_bytes(),
+        ],
+        bump,
+        space = 8 + ChildIndexState::MAX_SIZE,
+    )]
+    pub child_index: Box<Account<'info, ChildIndexState>>,
 
-    //NOTE: profile minting cost distribution account
 
-    // Current profile holders
-    ///CHECK:
-    pub current_parent_profile_holder: AccountInfo<'info>,
-    ///CHECK:
-    pub current_grand_parent_profile_holder: AccountInfo<'info>,
-    ///CHECK:
-    pub current_great_grand_parent_profile_holder: AccountInfo<'info>,
-    ///CHECK:
-    pub current_ggreat_grand_parent_profile_holder: AccountInfo<'info>,
-    ///CHECK:
-    pub current_genesis_profile_holder: AccountInfo<'info>,
+    //NOTE: profile minting cost distribution account
 
-    // Current Profile holder's opos token ata
+    // Minting cost is escrowed here and credited to the lineage earnings
     #[account(
-        mut,
-        token::mint = opos_token,
-        token::authority = user,
-        constraint= user_opos_ata.amount >= main_state.profile_minting_cost @ MyError::NotEnoughTokenToMint
+        init_if_needed,
+        payer = user,
+        associated_token::mint = opos_token,
+        associated_token::authority = main_state,
     )]
-    pub user_opos_ata: Box<Account<'info, TokenAccount>>,
-    ///CHECK:
+    pub opos_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    // The other ancestors earnings come in `remaining_accounts`
     #[account(
         mut,
-        constraint = init_ata_if_needed(
-            opos_token.to_account_info(),
-            parent_profile_holder_opos_ata.to_account_info(),
-            current_parent_profile_holder.to_account_info(),
-            user.to_account_info(),
-            token_program.to_account_info(),
-            system_program.to_account_info(),
-            associated_token_program.to_account_info(),
-        ) == Ok(())
-        // token::mint = opos_token,
-        // token::authority = current_parent_profile_holder,
+        seeds = [SEED_VAULT, main_state.genesis_profile.as_ref()],
+        bump = genesis_earnings._bump,
     )]
-    // pub parent_profile_holder_opos_ata: Box<Account<'info, TokenAccount>>,
-    pub parent_profile_holder_opos_ata: AccountInfo<'info>,
-    ///CHECK:
+    pub genesis_earnings: Box<Account<'info, EarningsState>>,
+
+    // Current Profile holder's opos token ata
     #[account(
         mut,
-        constraint = init_ata_if_needed(
-            opos_token.to_account_info(),
-            grand_parent_profile_holder_opos_ata.to_account_info(),
-            current_grand_parent_profile_holder.to_account_info(),
-            user.to_account_info(),
-            token_program.to_account_info(),
-            system_program.to_account_info(),
-            associated_token_program.to_account_info(),
-        ) == Ok(())
-        // token::mint = opos_token,
-        // token::authority = current_grand_parent_profile_holder,
+        token::mint = opos_token,
+        token::authority = user,
+        constraint= user_opos_ata.amount >= pricing_state.profile_minting_cost(
+            parent_profile_state.lineage.generation + 1
+        ) @ MyError::NotEnoughTokenToMint
     )]
-    pub grand_parent_profile_holder_opos_ata: AccountInfo<'info>,
-    ///CHECK:
+    pub user_opos_ata: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Paying in another mint than OPOS, native SOL is escrowed on `main_state` itself
     #[account(
-        mut,
-        constraint = init_ata_if_needed(
-            opos_token.to_account_info(),
-            great_grand_parent_profile_holder_opos_ata.to_account_info(),
-            current_great_grand_parent_profile_holder.to_account_info(),
-            user.to_account_info(),
-            token_program.to_account_info(),
-            system_program.to_account_info(),
-            associated_token_program.to_account_info(),
-        ) == Ok(())
-        // token::mint = opos_token,
-        // token::authority = current_great_grand_parent_profile_holder,
+        seeds = [SEED_PAYMENT_STATE],
+        bump = payment_state._bump,
     )]
-    pub great_grand_parent_profile_holder_opos_ata: AccountInfo<'info>,
-    ///CHECK:
+    pub payment_state: Option<Box<Account<'info, PaymentState>>>,
+
+    pub payment_mint: Option<Box<Account<'info, Mint>>>,
+
     #[account(
         mut,
-        constraint = init_ata_if_needed(
-            opos_token.to_account_info(),
-            ggreat_grand_parent_profile_holder_opos_ata.to_account_info(),
-            current_ggreat_grand_parent_profile_holder.to_account_info(),
-            user.to_account_info(),
-            token_program.to_account_info(),
-            system_program.to_account_info(),
-            associated_token_program.to_account_info(),
-        ) == Ok(())
-        // token::mint = opos_token,
-        // token::authority = current_ggreat_grand_parent_profile_holder,
+        token::mint = payment_mint,
+        token::authority = user,
     )]
-    pub ggreat_grand_parent_profile_holder_opos_ata: AccountInfo<'info>,
-    ///CHECK:
+    pub user_payment_ata: Option<Box<Account<'info, TokenAccount>>>,
+
     #[account(
-        mut,
-        constraint = init_ata_if_needed(
-            opos_token.to_account_info(),
-            genesis_profile_holder_opos_ata.to_account_info(),
-            current_genesis_profile_holder.to_account_info(),
-            user.to_account_info(),
-            token_program.to_account_info(),
-            system_program.to_account_info(),
-            associated_token_program.to_account_info(),
-        ) == Ok(())
-        // token::mint = opos_token,
-        // token::authority = current_genesis_profile_holder,
+        init_if_needed,
+        payer = user,
+        associated_token::mint = payment_mint,
+        associated_token::authority = main_state,
     )]
-    pub genesis_profile_holder_opos_ata: AccountInfo<'info>,
+    pub payment_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Pricing of curve priced payment mints
+    pub token_bonding: Option<Box<Account<'info, TokenBondingV0>>>,
+    pub curve: Option<Box<Account<'info, CurveV0>>>,
+    pub bonding_base_storage: Option<Box<Account<'info, TokenAccount>>>,
 }
 
 impl<'info> AMintProfileByAt<'info> {
-    pub fn mint(&mut self, name: String, symbol: String, uri_hash: String) -> Result<()> {
+    pub fn mint(
+        &mut self,
+        name: String,
+        symbol: String,
+        uri_hash: String,
+        max_payment: Option<u64>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
         let mint = self.profile.to_account_info();
         let user = self.user.to_account_info();
         let user_profile_ata = self.user_profile_ata.to_account_info();
@@ -374,69 +468,24 @@ impl<'info> AMintProfileByAt<'info> {
         //mint a token
         let cpi_acounts = MintTo {
             mint: mint.to_account_info(),
-            to: user_profile_ata,
-            authority: user.to_account_info(),
+            to: user_profile_ata.clone(),
+            authority: main_state.to_account_info(),
         };
         token::mint_to(
-            CpiContext::new(token_program.to_account_info(), cpi_acounts),
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                cpi_acounts,
+                &[&[SEED_MAIN_STATE, &[main_state._bump]]],
+            ),
             1,
         )?;
 
         // Creators Setup for royalty
-        let trading_price_distribution = main_state.trading_price_distribution;
-        let seller_fee_basis_points = TOTAL_SELLER_BASIS_POINTS - trading_price_distribution.seller;
-        let creators = vec![
-            //NOTE: currently not royalty info for creator
-            Creator {
-                address: user.key(),
-                verified: false,
-                share: 0,
-            },
-            Creator {
-                address: get_vault_pda(&self.profile_state.lineage.parent).0,
-                verified: false,
-                share: (trading_price_distribution.parent as u64 * 100u64
-                    / seller_fee_basis_points as u64) as u8,
-            },
-            Creator {
-                address: get_vault_pda(&self.profile_state.lineage.grand_parent).0,
-                verified: false,
-                share: (trading_price_distribution.grand_parent as u64 * 100u64
-                    / seller_fee_basis_points as u64) as u8,
-            },
-            Creator {
-                address: get_vault_pda(&self.profile_state.lineage.great_grand_parent).0,
-                verified: false,
-                share: (trading_price_distribution.great_grand_parent as u64 * 100u64
-                    / seller_fee_basis_points as u64) as u8,
-            },
-            Creator {
-                address: get_vault_pda(&main_state.genesis_profile).0,
-                verified: false,
-                share: (trading_price_distribution.genesis as u64 * 100u64
-                    / seller_fee_basis_points as u64) as u8,
-            },
-        ];
-
-        let mut unique_creators = HashMap::<Pubkey, Creator>::new();
-        for creator in creators.into_iter() {
-            let res = unique_creators.get_mut(&creator.address);
-            if let Some(value) = res {
-                value.share += creator.share;
-            } else {
-                unique_creators.insert(creator.address, creator);
-            }
-        }
-
-        let creators = Some(
-            unique_creators
-                .into_iter()
-                .map(|(k, v)| v)
-                .collect::<Vec<_>>(),
-        );
+        let (creators, seller_fee_basis_points) =
+            profile_creators(user.key(), &self.profile_state.lineage, main_state);
+        let creators = Some(creators);
 
-        let entry_point = "https://shdw-drive.genesysgo.net/FuBjTTmQuqM7pGR2gFsaiBxDmdj8ExP5fzNwnZyE2PgC/".to_string();
-        let uri = format!("{}{}", entry_point, uri_hash);
+        let uri = main_state.profile_uri(&uri_hash)?;
 
         let asset_data = AssetData {
             name,
@@ -460,7 +509,7 @@ impl<'info> AMintProfileByAt<'info> {
         let ix = Create {
             mint: mint.key(),
             payer: user.key(),
-            authority: user.key(),
+            authority: main_state.key(),
             initialize_mint: false,
             system_program: system_program.key(),
             metadata: metadata.key(),
@@ -478,118 +527,66 @@ impl<'info> AMintProfileByAt<'info> {
         .instruction();
 
         // NOTE: minting cost distribution
-        let sender_ata = self.user_opos_ata.to_account_info();
-        let cost = main_state.profile_minting_cost;
-        let minting_cost_distribution = main_state.minting_cost_distribution;
-
-
-        let mut transfer_data = Vec::new();
-
-        transfer_data.push(
-            TransferModel{
-               account_opos_ata: self
-               .parent_profile_holder_opos_ata.to_account_info(),
-               value: (cost as u128 * minting_cost_distribution.parent as u128
-                / TOTAL_SELLER_BASIS_POINTS as u128) as u64,
-                key: self
-                .parent_profile_holder_opos_ata
-                .to_account_info().key().to_string()
-            }
+        let cost = self
+            .pricing_state
+            .profile_minting_cost(self.profile_state.lineage.generation);
+        let (payment, cost) = self.charge(cost, max_payment)?;
+        self.collect_payment(payment, cost)?;
+        let main_state = &self.main_state;
+        let depth = main_state.lineage_depth as usize;
+        let (ancestor_costs, genesis_cost) =
+            main_state.lineage_distribution.split_cost(cost, depth)?;
+        require!(
+            remaining_accounts.len() == depth,
+            MyError::InvalidLineageAccounts
         );
+        let credit = |earnings: &mut EarningsState, source: EarningsSource, value: u64| {
+            match payment {
+                Some((index, _)) => earnings.record_payment(index, value),
+                None => earnings.record(source, value),
+            }
+        };
 
-        let gparent = TransferModel{
-            account_opos_ata: self
-            .grand_parent_profile_holder_opos_ata.to_account_info(),
-            value: (cost as u128 * minting_cost_distribution.grand_parent as u128
-             / TOTAL_SELLER_BASIS_POINTS as u128) as u64,
-             key: self
-             .grand_parent_profile_holder_opos_ata
-             .to_account_info().key().to_string()
-         };
-
-         let gparent_index = get_transfer_index(transfer_data.clone(), gparent.clone().key);
-        if gparent_index == -1 {
-            transfer_data.push(gparent)
-        } else {
-            transfer_data[gparent_index as usize].value = transfer_data[gparent_index as usize].value + gparent.value;
-        }
-
-
-        let ggparent = TransferModel{
-            account_opos_ata: self
-            .great_grand_parent_profile_holder_opos_ata.to_account_info(),
-            value: (cost as u128 * minting_cost_distribution.great_grand_parent as u128
-             / TOTAL_SELLER_BASIS_POINTS as u128) as u64,
-             key: self
-             .great_grand_parent_profile_holder_opos_ata
-             .to_account_info().key().to_string()
-         };
-
-         let ggparent_index = get_transfer_index(transfer_data.clone(), ggparent.clone().key);
-        if ggparent_index == -1 {
-            transfer_data.push(ggparent.clone())
-        } else {
-            transfer_data[ggparent_index as usize].value = transfer_data[ggparent_index as usize].value + ggparent.clone().value;
-        }
-
-
-        let gggparent = TransferModel{
-            account_opos_ata: self
-            .ggreat_grand_parent_profile_holder_opos_ata.to_account_info(),
-            value: (cost as u128 * minting_cost_distribution.ggreat_grand_parent as u128
-             / TOTAL_SELLER_BASIS_POINTS as u128) as u64,
-             key: self
-             .ggreat_grand_parent_profile_holder_opos_ata
-             .to_account_info().key().to_string()
-         };
-
-        let gggparent_index = get_transfer_index(transfer_data.clone(), gggparent.clone().key);
-        if gggparent_index == -1 {
-            transfer_data.push(gggparent.clone())
-        } else {
-            transfer_data[gggparent_index as usize].value = transfer_data[gggparent_index as usize].value + gggparent.clone().value;
-        }
-
-        let gensis = TransferModel{
-            account_opos_ata: self
-            .genesis_profile_holder_opos_ata.to_account_info(),
-            value: (cost as u128 * minting_cost_distribution.genesis as u128
-             / TOTAL_SELLER_BASIS_POINTS as u128) as u64,
-             key: self
-             .genesis_profile_holder_opos_ata
-             .to_account_info().key().to_string()
-         };
-
-         let gensis_index = get_transfer_index(transfer_data.clone(), gensis.clone().key);
-        if gensis_index == -1 {
-            transfer_data.push(gensis.clone())
-        } else {
-            transfer_data[gensis_index as usize].value = transfer_data[gensis_index as usize].value + gensis.clone().value;
-        }
-        
-
-        for transfer_item in transfer_data {
-            transfer_tokens(
-                sender_ata.to_account_info(),
-                transfer_item.account_opos_ata.to_account_info(),
-                user.to_account_info(),
-                token_program.to_account_info(),
-                transfer_item.value,
-            )?;
+        for (level, ((earnings, ancestor), value)) in remaining_accounts
+            .iter()
+            .zip(self.profile_state.ancestors)
+            .zip(ancestor_costs)
+            .enumerate()
+        {
+            require_keys_eq!(
+                earnings.key(),
+                get_vault_pda(&ancestor).0,
+                MyError::InvalidLineageAccounts
+            );
+            // `genesis_earnings` is written back on exit, keep its credit in one place
+            if ancestor == main_state.genesis_profile {
+                credit(&mut self.genesis_earnings, EarningsSource::Minting(level), value);
+                continue;
+            }
+            let mut earnings = Account::<EarningsState>::try_from(earnings)?;
+            if earnings.retired {
+                credit(&mut self.genesis_earnings, EarningsSource::Minting(level), value);
+                continue;
+            }
+            credit(&mut earnings, EarningsSource::Minting(level), value);
+            earnings.exit(&crate::ID)?;
         }
-    
-
+        credit(
+            &mut self.genesis_earnings,
+            EarningsSource::MintingAsGenesis,
+            genesis_cost,
+        );
 
         invoke_signed(
             &ix,
             &[
-                mint,
-                user,
+                mint.clone(),
+                user.clone(),
                 main_state.to_account_info(),
                 metadata,
-                edition,
-                mpl_program,
-                token_program,
+                edition.clone(),
+                mpl_program.clone(),
+                token_program.clone(),
                 system_program,
                 sysvar_instructions,
             ],
@@ -598,9 +595,80 @@ impl<'info> AMintProfileByAt<'info> {
             ],
         )?;
 
+        // mint and freeze authorities now sit with the master edition, keep the holder's
+        // account frozen under `main_state` so it only moves through `transfer_profile`
+        lock_profile(
+            user_profile_ata,
+            user,
+            mint,
+            edition,
+            &self.main_state,
+            mpl_program,
+            token_program,
+        )?;
+
         Ok(())
     }
 
+    /// the payment mint used and the amount charged for `cost`, `None` when paying in OPOS
+    pub fn charge(
+        &self,
+        cost: u64,
+        max_payment: Option<u64>,
+    ) -> Result<(Option<(usize, PaymentMint)>, u64)> {
+        let payment_mint = match &self.payment_mint {
+            Some(payment_mint) => payment_mint,
+            None => return Ok((None, cost)),
+        };
+        let payment_state = self
+            .payment_state
+            .as_ref()
+            .ok_or(MyError::InvalidPaymentAccounts)?;
+        let (index, payment, cost) = payment_state.charge(
+            payment_mint,
+            &Account::<Mint>::try_from(&self.opos_token)?,
+            cost,
+            max_payment,
+            self.token_bonding.as_deref(),
+            self.curve.as_deref(),
+            self.bonding_base_storage.as_deref(),
+        )?;
+        Ok((Some((index, payment)), cost))
+    }
+
+    /// escrows `amount` of the payment mint under `main_state`
+    pub fn collect_payment(
+        &mut self,
+        payment: Option<(usize, PaymentMint)>,
+        amount: u64,
+    ) -> Result<()> {
+        let user = self.user.to_account_info();
+        let (from, to) = match payment {
+            Some((_, payment)) if payment.is_native() => {
+                transfer_sol(
+                    user,
+                    self.main_state.to_account_info(),
+                    self.system_program.to_account_info(),
+                    amount,
+                )?;
+                self.main_state.escrowed_sol += amount;
+                return Ok(());
+            }
+            Some(_) => (&self.user_payment_ata, &self.payment_vault),
+            None => (&self.user_opos_ata, &self.opos_vault),
+        };
+        match (from, to) {
+            (Some(from), Some(to)) => transfer_tokens(
+                from.to_account_info(),
+                to.to_account_info(),
+                user,
+                self.token_program.to_account_info(),
+                amount,
+            ),
+            _ => err!(MyError::InvalidPaymentAccounts),
+        }
+    }
+
     pub fn verify_collection_item(&mut self, program_id: &Pubkey) -> Result<()> {
         let system_program = self.system_program.to_account_info();
         let token_program = self.token_program.to_account_info();
@@ -626,14 +694,20 @@ impl<'info> AMintProfileByAt<'info> {
         Ok(())
     }
 
+    /// the burned invitation is redeemed against `activation_token_issue`, the issue the
+    /// user received it from
     pub fn burn_activation_token(&mut self, program_id: &Pubkey) -> Result<()> {
+        let issue_key = self.activation_token_issue.key();
+        self.activation_token_receipt.redeem(
+            &issue_key,
+            &mut self.activation_token_issue,
+            Clock::get()?.unix_timestamp,
+        )?;
+
         let mint = self.activation_token.to_account_info();
         let user = self.user.to_account_info();
         let user_activation_token_ata = self.user_activation_token_ata.to_account_info();
-        let system_program = self.system_program.to_account_info();
         let token_program = self.token_program.to_account_info();
-        let mpl_program = self.mpl_program.to_account_info();
-        let sysvar_instructions = self.sysvar_instructions.to_account_info();
 
         let cpi_accounts = Burn {
             mint,
@@ -646,23 +720,3 @@ impl<'info> AMintProfileByAt<'info> {
     }
 
 }
-
-
-
-#[derive(Clone)]
-pub struct TransferModel<'info> {
-    pub account_opos_ata: AccountInfo<'info>,
-    pub key: String,
-    pub value: u64
-}
-
-pub fn get_transfer_index(datas: Vec<TransferModel>, key: String) -> i32 {
-    let mut indexer = -1;
-    for data in datas.into_iter().enumerate() {
-       if data.1.key == key {
-          indexer = data.0 as i32;
-          break
-       }
-    }
-    indexer
-}
\ No newline at end of file
@@ -3,3 +3,24 @@ pub use mint_genesis_profile::*;
 
 pub mod mint_profile_by_at;
 pub use mint_profile_by_at::*;
+
+pub mod migrate_profile_state;
+pub use migrate_profile_state::*;
+
+pub mod claim_earnings;
+pub use claim_earnings::*;
+
+pub mod transfer_profile;
+pub use transfer_profile::*;
+
+pub mod retire_profile;
+pub use retire_profile::*;
+
+pub mod import_profile;
+pub use import_profile::*;
+
+pub mod update_profile_metadata;
+pub use update_profile_metadata::*;
+
+pub mod profile_lut;
+pub use profile_lut::*;
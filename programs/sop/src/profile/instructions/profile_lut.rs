@@ -0,0 +1,192 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::get_associated_token_address, token::TokenAccount};
+use mpl_token_metadata::pda::{find_master_edition_account, find_metadata_account};
+use solana_address_lookup_table_program::{
+    instruction::{create_lookup_table_signed, extend_lookup_table},
+    state::AddressLookupTable,
+    ID as ADDRESS_LOOKUP_TABLE_PROGRAM,
+};
+use solana_program::program::invoke_signed;
+
+use crate::{
+    _main::MainState,
+    constants::{SEED_MAIN_STATE, SEED_PROFILE_STATE},
+    error::MyError,
+    profile::{ChildIndexState, ProfileState},
+    utils::{get_vault_pda, resolve_profile_holder},
+};
+
+/// Creates an address lookup table for `profile`, owned by `main_state`, holding the
+/// accounts needed to mint profiles and activation tokens under it.
+/// `recent_slot` has to be a recent slot, it seeds the table address.
+/// `remaining_accounts` as in `extend_profile_lut`.
+pub fn init_profile_lut<'info>(
+    ctx: Context<'_, '_, '_, 'info, AProfileLut<'info>>,
+    recent_slot: u64,
+) -> Result<()> {
+    require_keys_eq!(
+        ctx.accounts.profile_state.lut,
+        Pubkey::default(),
+        MyError::AlreadySet
+    );
+    let main_state = &ctx.accounts.main_state;
+    let (ix, lut) =
+        create_lookup_table_signed(main_state.key(), ctx.accounts.payer.key(), recent_slot);
+    require_keys_eq!(lut, ctx.accounts.lut.key(), MyError::InvalidLookupTable);
+    invoke_signed(
+        &ix,
+        &[
+            ctx.accounts.lut.to_account_info(),
+            main_state.to_account_info(),
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &[&[SEED_MAIN_STATE, &[main_state._bump]]],
+    )?;
+    ctx.accounts.profile_state.lut = lut;
+
+    ctx.accounts.extend(ctx.remaining_accounts, false)
+}
+
+/// Adds what is missing from the lookup table of `profile`, e.g. after a lineage profile
+/// changed hands. `remaining_accounts` are profile token accounts of the current lineage
+/// holders, their wallets and OPOS token accounts get added too.
+pub fn extend_profile_lut<'info>(
+    ctx: Context<'_, '_, '_, 'info, AProfileLut<'info>>,
+) -> Result<()> {
+    require_keys_eq!(
+        ctx.accounts.lut.key(),
+        ctx.accounts.profile_state.lut,
+        MyError::InvalidLookupTable
+    );
+    ctx.accounts.extend(ctx.remaining_accounts, true)
+}
+
+#[derive(Accounts)]
+pub struct AProfileLut<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_MAIN_STATE],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    ///CHECK: only used as the profile state seed
+    pub profile: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROFILE_STATE, profile.key().as_ref()],
+        bump,
+    )]
+    pub profile_state: Box<Account<'info, ProfileState>>,
+
+    ///CHECK: checked against the derived or the stored table address
+    #[account(mut)]
+    pub lut: AccountInfo<'info>,
+
+    ///CHECK:
+    #[account(address = ADDRESS_LOOKUP_TABLE_PROGRAM)]
+    pub address_lookup_table_program: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AProfileLut<'info> {
+    /// accounts of the profile and its lineage, plus those of the given lineage holders
+    pub fn addresses(&self, holder_atas: &[AccountInfo<'info>]) -> Result<Vec<Pubkey>> {
+        let main_state = &self.main_state;
+        let profile_state = &self.profile_state;
+        let profile = profile_state.mint;
+        let mut addresses = vec![
+            main_state.key(),
+            main_state.opos_token,
+            get_associated_token_address(&main_state.key(), &main_state.opos_token),
+            main_state.profile_collection,
+            find_metadata_account(&main_state.profile_collection).0,
+            find_master_edition_account(&main_state.profile_collection).0,
+            profile,
+            profile_state.key(),
+            find_metadata_account(&profile).0,
+            find_master_edition_account(&profile).0,
+            ChildIndexState::address(
+                &profile,
+                ChildIndexState::page_of(profile_state.lineage.total_child),
+            ),
+        ];
+
+        let mut lineage = vec![profile];
+        lineage.extend(profile_state.ancestors);
+        lineage.extend([
+            profile_state.lineage.parent,
+            profile_state.lineage.grand_parent,
+            profile_state.lineage.great_grand_parent,
+            profile_state.lineage.ggreat_grand_parent,
+            main_state.genesis_profile,
+        ]);
+        for mint in lineage.iter().filter(|mint| **mint != System::id()) {
+            addresses.push(*mint);
+            addresses.push(
+                Pubkey::find_program_address(&[SEED_PROFILE_STATE, mint.as_ref()], &crate::ID).0,
+            );
+            addresses.push(get_vault_pda(mint).0);
+        }
+
+        for holder_ata in holder_atas {
+            let token_account = Account::<TokenAccount>::try_from(holder_ata)?;
+            require!(
+                lineage.contains(&token_account.mint),
+                MyError::InvalidLineageHolder
+            );
+            let holder = resolve_profile_holder(&token_account, &token_account.mint)?;
+            addresses.push(holder_ata.key());
+            addresses.push(holder);
+            addresses.push(get_associated_token_address(
+                &holder,
+                &main_state.opos_token,
+            ));
+        }
+
+        let mut unique = Vec::with_capacity(addresses.len());
+        for address in addresses {
+            if !unique.contains(&address) {
+                unique.push(address);
+            }
+        }
+        Ok(unique)
+    }
+
+    /// extends the table with the addresses it doesn't hold yet
+    pub fn extend(&self, holder_atas: &[AccountInfo<'info>], existing: bool) -> Result<()> {
+        let mut addresses = self.addresses(holder_atas)?;
+        if existing {
+            let data = self.lut.try_borrow_data()?;
+            let table =
+                AddressLookupTable::deserialize(&data).map_err(|_| MyError::InvalidLookupTable)?;
+            addresses.retain(|address| !table.addresses.contains(address));
+        }
+        if addresses.is_empty() {
+            return Ok(());
+        }
+
+        let main_state = &self.main_state;
+        let ix = extend_lookup_table(
+            self.lut.key(),
+            main_state.key(),
+            Some(self.payer.key()),
+            addresses,
+        );
+        invoke_signed(
+            &ix,
+            &[
+                self.lut.to_account_info(),
+                main_state.to_account_info(),
+                self.payer.to_account_info(),
+                self.system_program.to_account_info(),
+            ],
+            &[&[SEED_MAIN_STATE, &[main_state._bump]]],
+        )?;
+        Ok(())
+    }
+}
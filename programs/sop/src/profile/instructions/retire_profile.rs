@@ -0,0 +1,227 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Burn, Mint, Token, TokenAccount},
+};
+use mpl_token_metadata::{
+    state::{EDITION, PREFIX as METADATA},
+    ID as MPL_ID,
+};
+use solana_program::program_option::COption;
+
+use crate::{
+    _main::MainState,
+    constants::{SEED_MAIN_STATE, SEED_PROFILE_STATE, SEED_VAULT},
+    error::MyError,
+    profile::{EarningsState, ProfileState},
+    utils::{transfer_tokens_from_main, unlock_profile},
+};
+
+/// Burns the profile nft and closes its `ProfileState`, refunding the rent to the holder.
+/// Called by the holder, or forced by the main state owner once the profile is locked, the
+/// nft of a profile minted before `lock_profile` existed can only be burned by its holder.
+/// Unclaimed earnings are paid out first, the earnings account stays around marked as
+/// retired so minting cost meant for it goes to genesis until the descendants are moved up
+/// with `reparent_profile`.
+/// Earnings in other payment mints have to be claimed beforehand.
+///
+/// The parent's `total_child` and child index are left as they are: the index slot keeps
+/// pointing at the retired mint so `descendants` still reaches its children, whose pages
+/// stay under that mint. Clients skip children whose profile state is closed.
+pub fn retire_profile(ctx: Context<ARetireProfile>) -> Result<()> {
+    let main_state = &ctx.accounts.main_state;
+    let earnings = &mut ctx.accounts.earnings;
+    require!(
+        !earnings.has_payment_unclaimed(),
+        MyError::UnclaimedPaymentEarnings
+    );
+    let amount = earnings.unclaimed;
+    if amount > 0 {
+        earnings.unclaimed = 0;
+        earnings.claimed += amount;
+        transfer_tokens_from_main(
+            ctx.accounts.opos_vault.to_account_info(),
+            ctx.accounts.holder_opos_ata.to_account_info(),
+            main_state,
+            ctx.accounts.token_program.to_account_info(),
+            amount,
+        )?;
+    }
+    earnings.retired = true;
+
+    let holder_ata = &ctx.accounts.holder_profile_ata;
+    let token_program = ctx.accounts.token_program.to_account_info();
+    if holder_ata.is_frozen() {
+        unlock_profile(
+            holder_ata.to_account_info(),
+            ctx.accounts.profile.to_account_info(),
+            ctx.accounts.profile_edition.to_account_info(),
+            main_state,
+            ctx.accounts.mpl_program.to_account_info(),
+            token_program.clone(),
+        )?;
+    }
+
+    let delegated_to_main = holder_ata.delegate == COption::Some(main_state.key())
+        && holder_ata.delegated_amount >= 1;
+    if delegated_to_main {
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.profile.to_account_info(),
+            from: holder_ata.to_account_info(),
+            authority: main_state.to_account_info(),
+        };
+        token::burn(
+            CpiContext::new_with_signer(
+                token_program,
+                cpi_accounts,
+                &[&[SEED_MAIN_STATE, &[main_state._bump]]],
+            ),
+            1,
+        )?;
+    } else {
+        // profiles minted before `lock_profile` existed can only be burned by the holder
+        require_keys_eq!(
+            ctx.accounts.authority.key(),
+            holder_ata.owner,
+            MyError::OnlyProfileHolderAllow
+        );
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.profile.to_account_info(),
+            from: holder_ata.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+        token::burn(CpiContext::new(token_program, cpi_accounts), 1)?;
+    }
+
+    Ok(())
+}
+
+/// Drops a retired ancestor from the lineage of `profile`, permissionless.
+/// Has to be called for every descendant within the lineage depth of the retired profile.
+pub fn reparent_profile(ctx: Context<AReparentProfile>) -> Result<()> {
+    let genesis_profile = ctx.accounts.main_state.genesis_profile;
+    let retired = ctx.accounts.retired_profile.key();
+    require!(
+        ctx.accounts
+            .profile_state
+            .remove_ancestor(retired, genesis_profile),
+        MyError::NotAnAncestor
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ARetireProfile<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == holder_profile_ata.owner
+            || authority.key() == main_state.owner @ MyError::OnlyProfileHolderAllow,
+    )]
+    pub authority: Signer<'info>,
+
+    ///CHECK: receives the rent and the unclaimed earnings
+    #[account(mut, address = holder_profile_ata.owner)]
+    pub holder: AccountInfo<'info>,
+
+    #[account(
+        seeds = [SEED_MAIN_STATE],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    #[account(
+        mut,
+        constraint = profile.key() != main_state.genesis_profile @ MyError::CannotRetireGenesis,
+    )]
+    pub profile: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROFILE_STATE, profile.key().as_ref()],
+        bump,
+        close = holder,
+    )]
+    pub profile_state: Box<Account<'info, ProfileState>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_VAULT, profile.key().as_ref()],
+        bump = earnings._bump,
+    )]
+    pub earnings: Box<Account<'info, EarningsState>>,
+
+    ///CHECK:
+    #[account(
+        seeds=[
+            METADATA.as_ref(),
+            MPL_ID.as_ref(),
+            profile.key().as_ref(),
+            EDITION.as_ref(),
+        ],
+        bump,
+        seeds::program = MPL_ID
+    )]
+    pub profile_edition: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        token::mint = profile,
+        constraint = holder_profile_ata.amount == 1 @ MyError::OnlyProfileHolderAllow,
+    )]
+    pub holder_profile_ata: Box<Account<'info, TokenAccount>>,
+
+    ///CHECK:
+    #[account(address = main_state.opos_token)]
+    pub opos_token: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = opos_token,
+        associated_token::authority = main_state,
+    )]
+    pub opos_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = opos_token,
+        associated_token::authority = holder,
+    )]
+    pub holder_opos_ata: Box<Account<'info, TokenAccount>>,
+
+    ///CHECK:
+    #[account(address = MPL_ID)]
+    pub mpl_program: AccountInfo<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AReparentProfile<'info> {
+    #[account(
+        seeds = [SEED_MAIN_STATE],
+        bump,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    ///CHECK: only used as the profile state seed
+    pub profile: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROFILE_STATE, profile.key().as_ref()],
+        bump,
+    )]
+    pub profile_state: Box<Account<'info, ProfileState>>,
+
+    ///CHECK: only used as the earnings seed
+    pub retired_profile: AccountInfo<'info>,
+
+    #[account(
+        seeds = [SEED_VAULT, retired_profile.key().as_ref()],
+        bump = retired_earnings._bump,
+        constraint = retired_earnings.retired @ MyError::ProfileNotRetired,
+    )]
+    pub retired_earnings: Box<Account<'info, EarningsState>>,
+}
@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, Mint, Token, TokenAccount, Transfer},
+};
+use mpl_token_metadata::{
+    state::{EDITION, PREFIX as METADATA},
+    ID as MPL_ID,
+};
+
+use crate::{
+    _main::MainState,
+    constants::{PAUSE_PROFILES, SEED_MAIN_STATE, SEED_PROFILE_STATE},
+    error::MyError,
+    profile::ProfileState,
+    utils::{lock_profile, unlock_profile},
+};
+
+/// Moves the profile nft to `receiver` and records them as `ProfileState.current_holder`.
+/// The receiver signs too, their account gets delegated to `main_state` and frozen again.
+pub fn transfer_profile(ctx: Context<ATransferProfile>) -> Result<()> {
+    let profile = ctx.accounts.profile.to_account_info();
+    let profile_edition = ctx.accounts.profile_edition.to_account_info();
+    let mpl_program = ctx.accounts.mpl_program.to_account_info();
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let holder_ata = ctx.accounts.holder_profile_ata.to_account_info();
+    let receiver_ata = ctx.accounts.receiver_profile_ata.to_account_info();
+
+    // profiles minted before `lock_profile` existed are not frozen
+    if ctx.accounts.holder_profile_ata.is_frozen() {
+        unlock_profile(
+            holder_ata.clone(),
+            profile.clone(),
+            profile_edition.clone(),
+            &ctx.accounts.main_state,
+            mpl_program.clone(),
+            token_program.clone(),
+        )?;
+    }
+
+    let cpi_accounts = Transfer {
+        from: holder_ata,
+        to: receiver_ata.clone(),
+        authority: ctx.accounts.holder.to_account_info(),
+    };
+    token::transfer(CpiContext::new(token_program.clone(), cpi_accounts), 1)?;
+
+    lock_profile(
+        receiver_ata,
+        ctx.accounts.receiver.to_account_info(),
+        profile,
+        profile_edition,
+        &ctx.accounts.main_state,
+        mpl_program,
+        token_program,
+    )?;
+
+    ctx.accounts.profile_state.current_holder = ctx.accounts.receiver.key();
+    Ok(())
+}
+
+/// Points `ProfileState.current_holder` at the owner of `holder_profile_ata`, who signs.
+/// Profiles minted before `lock_profile` existed can move without `transfer_profile`.
+pub fn sync_profile_holder(ctx: Context<ASyncProfileHolder>) -> Result<()> {
+    ctx.accounts.profile_state.current_holder = ctx.accounts.holder_profile_ata.owner;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ATransferProfile<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    pub receiver: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_MAIN_STATE],
+        bump,
+        constraint = !main_state.is_paused(PAUSE_PROFILES) @ MyError::ProfilesPaused,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    pub profile: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROFILE_STATE, profile.key().as_ref()],
+        bump,
+    )]
+    pub profile_state: Box<Account<'info, ProfileState>>,
+
+    ///CHECK:
+    #[account(
+        seeds=[
+            METADATA.as_ref(),
+            MPL_ID.as_ref(),
+            profile.key().as_ref(),
+            EDITION.as_ref(),
+        ],
+        bump,
+        seeds::program = MPL_ID
+    )]
+    pub profile_edition: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        token::mint = profile,
+        token::authority = holder,
+        constraint = holder_profile_ata.amount == 1 @ MyError::OnlyProfileHolderAllow,
+    )]
+    pub holder_profile_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = holder,
+        associated_token::mint = profile,
+        associated_token::authority = receiver,
+    )]
+    pub receiver_profile_ata: Box<Account<'info, TokenAccount>>,
+
+    ///CHECK:
+    #[account(address = MPL_ID)]
+    pub mpl_program: AccountInfo<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ASyncProfileHolder<'info> {
+    #[account(address = holder_profile_ata.owner @ MyError::OnlyProfileHolderAllow)]
+    pub holder: Signer<'info>,
+
+    pub profile: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROFILE_STATE, profile.key().as_ref()],
+        bump,
+    )]
+    pub profile_state: Box<Account<'info, ProfileState>>,
+
+    #[account(
+        token::mint = profile,
+        constraint = holder_profile_ata.amount == 1 @ MyError::OnlyProfileHolderAllow,
+    )]
+    pub holder_profile_ata: Box<Account<'info, TokenAccount>>,
+}
@@ -0,0 +1,178 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use mpl_token_metadata::{
+    instruction::{
+        builders::Update, CollectionDetailsToggle, CollectionToggle, InstructionBuilder,
+        RuleSetToggle, UpdateArgs, UsesToggle,
+    },
+    state::{Data, Metadata, TokenMetadataAccount, EDITION, PREFIX as METADATA},
+    ID as MPL_ID,
+};
+use solana_program::program::invoke_signed;
+
+use crate::{_main::MainState, constants::{PAUSE_PROFILES, SEED_MAIN_STATE}, error::MyError};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone)]
+pub struct UpdateProfileMetadataInput {
+    /// `None` keeps the current value
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub uri: Option<String>,
+}
+
+/// Lets the profile holder change name, symbol and uri within `MainState.metadata_constraints`
+pub fn update_profile_metadata(
+    ctx: Context<AUpdateProfileMetadata>,
+    input: UpdateProfileMetadataInput,
+) -> Result<()> {
+    let constraints = ctx.accounts.main_state.metadata_constraints;
+    let metadata = Metadata::from_account_info(&ctx.accounts.profile_metadata)
+        .map_err(|_| MyError::UnknownNft)?;
+    let current = metadata.data;
+
+    let name = match input.name {
+        Some(name) => {
+            constraints.check_name(&name)?;
+            name
+        }
+        None => current.name.trim_matches('\0').to_string(),
+    };
+    let symbol = match input.symbol {
+        Some(symbol) => {
+            constraints.check_symbol(&symbol)?;
+            symbol
+        }
+        None => current.symbol.trim_matches('\0').to_string(),
+    };
+    let uri = match input.uri {
+        Some(uri) => {
+            constraints.check_uri(&uri)?;
+            uri
+        }
+        None => current.uri.trim_matches('\0').to_string(),
+    };
+
+    ctx.accounts.update(Data {
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points: current.seller_fee_basis_points,
+        creators: current.creators,
+    })
+}
+
+#[derive(Accounts)]
+pub struct AUpdateProfileMetadata<'info> {
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        token::mint = profile,
+        token::authority = holder,
+        constraint = holder_profile_ata.amount == 1 @ MyError::OnlyProfileHolderAllow,
+    )]
+    pub holder_profile_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [SEED_MAIN_STATE],
+        bump,
+        constraint = !main_state.is_paused(PAUSE_PROFILES) @ MyError::ProfilesPaused,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+
+    ///CHECK: checked through `holder_profile_ata` and the metadata seeds
+    pub profile: AccountInfo<'info>,
+
+    ///CHECK:
+    #[account(
+        mut,
+        seeds=[
+            METADATA.as_ref(),
+            MPL_ID.as_ref(),
+            profile.key().as_ref(),
+        ],
+        bump,
+        seeds::program = MPL_ID
+    )]
+    pub profile_metadata: AccountInfo<'info>,
+
+    ///CHECK:
+    #[account(
+        seeds=[
+            METADATA.as_ref(),
+            MPL_ID.as_ref(),
+            profile.key().as_ref(),
+            EDITION.as_ref(),
+        ],
+        bump,
+        seeds::program = MPL_ID
+    )]
+    pub profile_edition: AccountInfo<'info>,
+
+    ///CHECK:
+    #[account()]
+    pub sysvar_instructions: AccountInfo<'info>,
+
+    ///CHECK:
+    #[account(address = MPL_ID)]
+    pub mpl_program: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AUpdateProfileMetadata<'info> {
+    pub fn update(&self, data: Data) -> Result<()> {
+        let mint = self.profile.to_account_info();
+        let payer = self.holder.to_account_info();
+        let metadata = self.profile_metadata.to_account_info();
+        let edition = self.profile_edition.to_account_info();
+        let mpl_program = self.mpl_program.to_account_info();
+        let system_program = self.system_program.to_account_info();
+        let sysvar_instructions = self.sysvar_instructions.to_account_info();
+        let main_state = &self.main_state;
+
+        let args = UpdateArgs::V1 {
+            new_update_authority: None,
+            data: Some(data),
+            primary_sale_happened: None,
+            is_mutable: None,
+            collection: CollectionToggle::None,
+            collection_details: CollectionDetailsToggle::None,
+            uses: UsesToggle::None,
+            rule_set: RuleSetToggle::None,
+            authorization_data: None,
+        };
+
+        let ix = Update {
+            mint: mint.key(),
+            metadata: metadata.key(),
+            edition: Some(edition.key()),
+            token: None,
+            payer: payer.key(),
+            args,
+            authority: main_state.key(),
+            delegate_record: None,
+            authorization_rules: None,
+            authorization_rules_program: None,
+            system_program: system_program.key(),
+            sysvar_instructions: sysvar_instructions.key(),
+        }
+        .instruction();
+
+        invoke_signed(
+            &ix,
+            &[
+                mint,
+                payer,
+                metadata,
+                edition,
+                mpl_program,
+                system_program,
+                sysvar_instructions,
+                main_state.to_account_info(),
+            ],
+            &[&[SEED_MAIN_STATE, &[main_state._bump]]],
+        )?;
+
+        Ok(())
+    }
+}
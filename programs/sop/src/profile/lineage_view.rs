@@ -0,0 +1,153 @@
+//! Lineage queries over account data. Loading accounts is left to the caller, so the same
+//! code runs in the program (from `AccountInfo`s) and in off-chain clients (from RPC).
+
+use std::collections::VecDeque;
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::MAX_LINEAGE_DEPTH,
+    profile::{ChildIndexState, ProfileState},
+};
+
+/// Ancestors of `profile`, parent first, up to and including the genesis profile but at most
+/// `MAX_LINEAGE_DEPTH` of them. `load_profile` returns the `ProfileState` of a profile mint,
+/// the walk stops at the first one it can't load (e.g. a retired profile) and when the
+/// lineage loops back to `profile`.
+pub fn ancestor_chain<F>(profile: &ProfileState, mut load_profile: F) -> Vec<Pubkey>
+where
+    F: FnMut(&Pubkey) -> Option<ProfileState>,
+{
+    let mut chain = Vec::new();
+    let mut current = profile.mint;
+    let mut parent = profile.lineage.parent;
+    // the genesis profile is its own parent
+    while parent != current
+        && parent != profile.mint
+        && parent != Pubkey::default()
+        && chain.len() < MAX_LINEAGE_DEPTH
+    {
+        chain.push(parent);
+        let parent_state = match load_profile(&parent) {
+            Some(state) => state,
+            None => break,
+        };
+        current = parent;
+        parent = parent_state.lineage.parent;
+    }
+    chain
+}
+
+/// Descendants of `root` in breadth first order, skipping the first `offset` and returning
+/// at most `limit`. `load_page(parent, page)` returns a `ChildIndexState` page, `None` past
+/// the last one. Retired profiles stay in the index, so they are returned as well.
+pub fn descendants<F>(root: Pubkey, offset: usize, limit: usize, mut load_page: F) -> Vec<Pubkey>
+where
+    F: FnMut(&Pubkey, u64) -> Option<ChildIndexState>,
+{
+    let mut found = Vec::new();
+    let mut skipped = 0;
+    let mut queue = VecDeque::from([root]);
+    while let Some(parent) = queue.pop_front() {
+        let mut page = 0;
+        while let Some(index) = load_page(&parent, page) {
+            for child in index.children() {
+                if found.len() == limit {
+                    return found;
+                }
+                if skipped < offset {
+                    skipped += 1;
+                } else {
+                    found.push(child);
+                }
+                queue.push_back(child);
+            }
+            page += 1;
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::constants::{CHILD_INDEX_PAGE_SIZE, MAX_LINEAGE_DEPTH};
+    use crate::other_states::LineageInfo;
+
+    fn profile(mint: Pubkey, parent: Pubkey) -> ProfileState {
+        ProfileState {
+            lineage: LineageInfo {
+                parent,
+                ..Default::default()
+            },
+            mint,
+            activation_token: None,
+            total_minted_sft: 0,
+            total_minted_offers: 0,
+            lut: Pubkey::default(),
+            version: ProfileState::VERSION,
+            ancestors: [Pubkey::default(); MAX_LINEAGE_DEPTH],
+            current_holder: Pubkey::default(),
+            invitation_window: Default::default(),
+        }
+    }
+
+    #[test]
+    fn walks_ancestors_and_descendants() {
+        let genesis = Pubkey::new_unique();
+        let mut profiles = HashMap::new();
+        let mut pages = HashMap::<(Pubkey, u64), ChildIndexState>::new();
+        let mut add = |mint: Pubkey, parent: Pubkey, child: u64| {
+            profiles.insert(mint, profile(mint, parent));
+            let index = pages
+                .entry((parent, ChildIndexState::page_of(child)))
+                .or_insert_with(|| ChildIndexState {
+                    parent,
+                    page: 0,
+                    _bump: 0,
+                    children: [Pubkey::default(); CHILD_INDEX_PAGE_SIZE],
+                });
+            index.push(parent, child, mint, 0);
+        };
+
+        // genesis gets a second page of children, the last one has a child of its own
+        let children = (0..CHILD_INDEX_PAGE_SIZE as u64 + 1)
+            .map(|child| {
+                let mint = Pubkey::new_unique();
+                add(mint, genesis, child);
+                mint
+            })
+            .collect::<Vec<_>>();
+        let grand_child = Pubkey::new_unique();
+        add(grand_child, children[CHILD_INDEX_PAGE_SIZE], 0);
+        profiles.insert(genesis, profile(genesis, genesis));
+
+        let chain = ancestor_chain(&profiles[&grand_child], |mint| profiles.get(mint).cloned());
+        assert_eq!(chain, vec![children[CHILD_INDEX_PAGE_SIZE], genesis]);
+
+        // a lineage looping back on itself
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        profiles.insert(a, profile(a, b));
+        profiles.insert(b, profile(b, a));
+        let chain = ancestor_chain(&profiles[&a], |mint| profiles.get(mint).cloned());
+        assert_eq!(chain, vec![b]);
+
+        // a lineage deeper than the walk goes
+        let mints = (0..MAX_LINEAGE_DEPTH + 2)
+            .map(|_| Pubkey::new_unique())
+            .collect::<Vec<_>>();
+        for pair in mints.windows(2) {
+            profiles.insert(pair[0], profile(pair[0], pair[1]));
+        }
+        let chain = ancestor_chain(&profiles[&mints[0]], |mint| profiles.get(mint).cloned());
+        assert_eq!(chain, mints[1..=MAX_LINEAGE_DEPTH]);
+
+        let load_page = |parent: &Pubkey, page: u64| pages.get(&(*parent, page)).cloned();
+        let all = descendants(genesis, 0, usize::MAX, load_page);
+        assert_eq!(all.len(), children.len() + 1);
+        assert_eq!(all[..children.len()], children[..]);
+        assert_eq!(descendants(genesis, children.len(), 5, load_page), vec![grand_child]);
+    }
+}
@@ -3,3 +3,14 @@ pub use instructions::*;
 
 pub mod profile_state;
 pub use profile_state::ProfileState;
+
+pub mod earnings_state;
+pub use earnings_state::{record_earnings, EarningsSource, EarningsState};
+
+pub mod child_index_state;
+pub use child_index_state::ChildIndexState;
+
+pub mod generation_state;
+pub use generation_state::GenerationState;
+
+pub mod lineage_view;
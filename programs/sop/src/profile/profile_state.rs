@@ -1,4 +1,8 @@
-use crate::other_states::LineageInfo;
+use crate::{
+    constants::MAX_LINEAGE_DEPTH,
+    error::MyError,
+    other_states::{InvitationWindow, LineageInfo},
+};
 use anchor_lang::prelude::*;
 
 #[account]
@@ -9,8 +13,106 @@ pub struct ProfileState {
     pub total_minted_sft: u64,
     pub total_minted_offers: u64,
     pub lut: Pubkey,
+    /// layout version, bumped by `migrate_profile_state`
+    pub version: u8,
+    /// `ancestors[0]` is the parent, levels above the genesis profile point to genesis
+    pub ancestors: [Pubkey; MAX_LINEAGE_DEPTH],
+    /// wallet holding the profile nft, kept current by `transfer_profile`
+    pub current_holder: Pubkey,
+    /// invitations minted in the current `invitation_quota` window
+    pub invitation_window: InvitationWindow,
 }
 
 impl ProfileState {
     pub const MAX_SIZE: usize = std::mem::size_of::<Self>();
-}
\ No newline at end of file
+    pub const VERSION: u8 = 4;
+
+    /// ancestors of a profile minted under this one
+    pub fn child_ancestors(&self, genesis_profile: Pubkey) -> [Pubkey; MAX_LINEAGE_DEPTH] {
+        let mut ancestors = [genesis_profile; MAX_LINEAGE_DEPTH];
+        ancestors[0] = self.mint;
+        for (ancestor, parent_ancestor) in ancestors[1..].iter_mut().zip(self.ancestors) {
+            if parent_ancestor != System::id() {
+                *ancestor = parent_ancestor;
+            }
+        }
+        ancestors
+    }
+
+    /// wallet the profile's payouts go to, profiles minted before `current_holder` existed
+    /// need `migrate_profile_state` or `sync_profile_holder` first
+    pub fn holder(&self) -> Result<Pubkey> {
+        require!(
+            self.current_holder != Pubkey::default(),
+            MyError::InvalidLineageHolder
+        );
+        Ok(self.current_holder)
+    }
+
+    /// drops a retired profile from the lineage, the ancestors above it move down a level
+    /// and genesis fills the top. Returns false if `retired` is not an ancestor.
+    pub fn remove_ancestor(&mut self, retired: Pubkey, genesis_profile: Pubkey) -> bool {
+        let level = match self.ancestors.iter().position(|ancestor| *ancestor == retired) {
+            Some(level) => level,
+            None => return false,
+        };
+        self.ancestors[level..].rotate_left(1);
+        self.ancestors[MAX_LINEAGE_DEPTH - 1] = genesis_profile;
+
+        self.lineage.parent = self.ancestors[0];
+        self.lineage.grand_parent = self.ancestors[1];
+        self.lineage.great_grand_parent = self.ancestors[2];
+        self.lineage.ggreat_grand_parent = self.ancestors[3];
+        true
+    }
+
+    /// fills `ancestors` of profiles minted before it existed from `lineage`
+    pub fn backfill_ancestors(&mut self, genesis_profile: Pubkey) {
+        if self.ancestors[0] != System::id() {
+            return;
+        }
+        self.ancestors = [genesis_profile; MAX_LINEAGE_DEPTH];
+        self.ancestors[..4].copy_from_slice(&[
+            self.lineage.parent,
+            self.lineage.grand_parent,
+            self.lineage.great_grand_parent,
+            self.lineage.ggreat_grand_parent,
+        ]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_ancestor_moves_lineage_down() {
+        let genesis = Pubkey::new_unique();
+        let retired = Pubkey::new_unique();
+        let parent = Pubkey::new_unique();
+        let mut ancestors = [genesis; MAX_LINEAGE_DEPTH];
+        ancestors[..2].copy_from_slice(&[parent, retired]);
+        let mut state = ProfileState {
+            lineage: LineageInfo::default(),
+            mint: Pubkey::new_unique(),
+            activation_token: None,
+            total_minted_sft: 0,
+            total_minted_offers: 0,
+            lut: Pubkey::default(),
+            version: ProfileState::VERSION,
+            ancestors,
+            current_holder: Pubkey::default(),
+            invitation_window: InvitationWindow::default(),
+        };
+
+        assert!(!state.remove_ancestor(Pubkey::new_unique(), genesis));
+        assert!(state.remove_ancestor(retired, genesis));
+        assert_eq!(state.ancestors, {
+            let mut expected = [genesis; MAX_LINEAGE_DEPTH];
+            expected[0] = parent;
+            expected
+        });
+        assert_eq!(state.lineage.parent, parent);
+        assert_eq!(state.lineage.grand_parent, genesis);
+    }
+}
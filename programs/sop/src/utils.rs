@@ -1,14 +1,19 @@
 use crate::{
     _main::main_state::MainState,
-    constants::{SEED_MAIN_STATE, SEED_VAULT},
+    constants::{SEED_MAIN_STATE, SEED_PROFILE_STATE, SEED_VAULT},
     error::MyError,
+    profile::ProfileState,
 };
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer as SystemTransfer};
 use anchor_spl::associated_token::{create as create_ata, Create as CreateAta};
-use anchor_spl::token::{self, initialize_account, Token, Transfer};
+use anchor_spl::token::{self, initialize_account, Approve, Token, TokenAccount, Transfer};
 
 use mpl_token_metadata::{
-    instruction::{builders::Verify, verify_sized_collection_item, InstructionBuilder},
+    instruction::{
+        builders::Verify, freeze_delegated_account, thaw_delegated_account,
+        verify_sized_collection_item, InstructionBuilder,
+    },
     state::{Metadata, TokenMetadataAccount},
 };
 use solana_program::program::{invoke, invoke_signed};
@@ -52,6 +57,29 @@ pub fn transfer_tokens_from_main<'info>(
     Ok(())
 }
 
+pub fn transfer_sol<'info>(
+    from: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = SystemTransfer { from, to };
+    system_program::transfer(CpiContext::new(system_program, cpi_accounts), amount)?;
+    Ok(())
+}
+
+/// Pays out SOL escrowed on the main state account itself
+pub fn transfer_sol_from_main<'info>(
+    main: &Account<'info, MainState>,
+    to: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let main = main.to_account_info();
+    **main.try_borrow_mut_lamports()? -= amount;
+    **to.try_borrow_mut_lamports()? += amount;
+    Ok(())
+}
+
 pub fn verify_collection_item_by_main<'info>(
     metadata: AccountInfo<'info>,
     collection: AccountInfo<'info>,
@@ -96,6 +124,77 @@ pub fn verify_collection_item_by_main<'info>(
     Ok(())
 }
 
+/// Delegates the profile token account of `holder` to `main_state` and freezes it, so the
+/// profile only moves through `transfer_profile`. `holder` has to sign.
+pub fn lock_profile<'info>(
+    holder_ata: AccountInfo<'info>,
+    holder: AccountInfo<'info>,
+    profile: AccountInfo<'info>,
+    profile_edition: AccountInfo<'info>,
+    main_state: &Account<'info, MainState>,
+    mpl_program: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    let cpi_accounts = Approve {
+        to: holder_ata.clone(),
+        delegate: main_state.to_account_info(),
+        authority: holder,
+    };
+    token::approve(CpiContext::new(token_program.clone(), cpi_accounts), 1)?;
+
+    let ix = freeze_delegated_account(
+        mpl_program.key(),
+        main_state.key(),
+        holder_ata.key(),
+        profile_edition.key(),
+        profile.key(),
+    );
+    invoke_signed(
+        &ix,
+        &[
+            main_state.to_account_info(),
+            holder_ata,
+            profile_edition,
+            profile,
+            token_program,
+            mpl_program,
+        ],
+        &[&[SEED_MAIN_STATE, &[main_state._bump]]],
+    )?;
+    Ok(())
+}
+
+/// Thaws a profile token account frozen by `lock_profile`.
+pub fn unlock_profile<'info>(
+    holder_ata: AccountInfo<'info>,
+    profile: AccountInfo<'info>,
+    profile_edition: AccountInfo<'info>,
+    main_state: &Account<'info, MainState>,
+    mpl_program: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    let ix = thaw_delegated_account(
+        mpl_program.key(),
+        main_state.key(),
+        holder_ata.key(),
+        profile_edition.key(),
+        profile.key(),
+    );
+    invoke_signed(
+        &ix,
+        &[
+            main_state.to_account_info(),
+            holder_ata,
+            profile_edition,
+            profile,
+            token_program,
+            mpl_program,
+        ],
+        &[&[SEED_MAIN_STATE, &[main_state._bump]]],
+    )?;
+    Ok(())
+}
+
 pub fn get_vault_pda(profile_mint: &Pubkey) -> (Pubkey, u8) {
     let res = Pubkey::find_program_address(&[SEED_VAULT, profile_mint.as_ref()], &crate::ID);
     // let sign_seed = [SEED_VAULT, profile_mint.as_ref(), &[res.1]].as_ref();
@@ -103,6 +202,33 @@ pub fn get_vault_pda(profile_mint: &Pubkey) -> (Pubkey, u8) {
     res
 }
 
+/// Resolves the current holder of the `profile` nft from the token account holding it.
+/// Used wherever the lineage gets paid, so payouts can't be routed to arbitrary keys.
+pub fn resolve_profile_holder(holder_ata: &TokenAccount, profile: &Pubkey) -> Result<Pubkey> {
+    require!(
+        holder_ata.mint == *profile && holder_ata.amount == 1,
+        MyError::InvalidLineageHolder
+    );
+    Ok(holder_ata.owner)
+}
+
+/// current holder of `profile` read from its `profile_state`, `None` when that state has
+/// been closed by `retire_profile`
+pub fn lineage_holder(profile_state: &AccountInfo, profile: &Pubkey) -> Result<Option<Pubkey>> {
+    let (profile_state_key, _) =
+        Pubkey::find_program_address(&[SEED_PROFILE_STATE, profile.as_ref()], &crate::ID);
+    require_keys_eq!(
+        profile_state.key(),
+        profile_state_key,
+        MyError::InvalidLineageHolder
+    );
+    if profile_state.data_is_empty() {
+        return Ok(None);
+    }
+    let profile_state = Account::<ProfileState>::try_from(profile_state)?;
+    Ok(Some(profile_state.holder()?))
+}
+
 pub fn _verify_collection(metadata_account: &AccountInfo, collection_id: Pubkey) -> Result<()> {
     let metadata =
         Metadata::from_account_info(metadata_account).map_err(|_| MyError::UnknownNft)?;
@@ -123,6 +249,18 @@ pub fn init_ata_if_needed<'info>(
     associated_token_program: AccountInfo<'info>,
 ) -> Result<()> {
     if *ata.owner == token::ID {
+        // already there, make sure it's really `authority`'s account for `mint`
+        let token_account = Account::<TokenAccount>::try_from(&ata)?;
+        require_keys_eq!(
+            token_account.mint,
+            mint.key(),
+            anchor_lang::error::ErrorCode::ConstraintTokenMint
+        );
+        require_keys_eq!(
+            token_account.owner,
+            authority.key(),
+            anchor_lang::error::ErrorCode::ConstraintTokenOwner
+        );
         return Ok(());
     }
 
@@ -138,3 +276,46 @@ pub fn init_ata_if_needed<'info>(
     create_ata(CpiContext::new(associated_token_program, cpi_accounts))?;
     Ok(())
 }
+
+/// Grows `account` to `new_size`, topping up rent from `payer`.
+pub fn realloc_if_needed<'info>(
+    account: AccountInfo<'info>,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    new_size: usize,
+) -> Result<()> {
+    if account.data_len() >= new_size {
+        return Ok(());
+    }
+
+    let rent = Rent::get()?.minimum_balance(new_size);
+    let lamports = rent.saturating_sub(account.lamports());
+    if lamports > 0 {
+        let cpi_accounts = SystemTransfer {
+            from: payer,
+            to: account.clone(),
+        };
+        system_program::transfer(CpiContext::new(system_program, cpi_accounts), lamports)?;
+    }
+
+    account.realloc(new_size, true)?;
+    Ok(())
+}
+
+/// Grows a program account of type `T` to `new_size` and lets `migrate` fill in the fields
+/// added since it was created. Those fields start out zeroed.
+pub fn migrate_account<'info, T: AccountSerialize + AccountDeserialize>(
+    account: AccountInfo<'info>,
+    payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    new_size: usize,
+    migrate: impl FnOnce(&mut T),
+) -> Result<()> {
+    realloc_if_needed(account.clone(), payer, system_program, new_size)?;
+
+    let mut data = account.try_borrow_mut_data()?;
+    let mut state = T::try_deserialize(&mut &data[..])?;
+    migrate(&mut state);
+    state.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
@@ -5,15 +5,17 @@ import { utf8 } from "@coral-xyz/anchor/dist/cjs/utils/bytes";
 import { TOKEN_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/utils/token";
 import { IDL, Sop } from "../target/types/sop";
 import {
-  LineageInfo,
   MainState,
   MainStateInput,
+  ImportProfileInput,
   MintProfileByAdminInput,
+  PaymentInput,
+  PricingInput,
   Result,
   TxPassType,
 } from "./web3Types";
 import Config from "./web3Config.json";
-import { getAssociatedTokenAddressSync, getNonTransferable, unpackAccount } from "@solana/spl-token";
+import { getAssociatedTokenAddressSync, getNonTransferable, NATIVE_MINT } from "@solana/spl-token";
 import { BaseMpl } from "./base/baseMpl";
 import { web3Consts } from './web3Consts'
 import { BaseSpl } from "./base/baseSpl";
@@ -29,6 +31,24 @@ const {
 } = web3Consts;
 const log = console.log;
 
+const RoleSeeds = {
+  collectionManager: "collection_manager",
+  feeManager: "fee_manager",
+  pauser: "pauser",
+  communityManager: "community_manager",
+};
+export type Role = keyof typeof RoleSeeds;
+
+export type CollectionType = "root" | "profile" | "badge" | "activationToken" | "pass";
+
+export const PauseFlags = {
+  profiles: 1 << 0,
+  activationTokens: 1 << 1,
+  collections: 1 << 2,
+  curveBuy: 1 << 3,
+  curveSell: 1 << 4,
+};
+
 export function sleep(ms: number) {
   return new Promise(resolve => setTimeout(resolve, ms));
 }
@@ -42,6 +62,8 @@ export class Connectivity {
   program: Program<Sop>;
   owner: web3.PublicKey;
   mainState: web3.PublicKey;
+  pricingState: web3.PublicKey;
+  paymentState: web3.PublicKey;
   connection: web3.Connection;
   baseSpl: BaseSpl;
 
@@ -62,6 +84,14 @@ export class Connectivity {
       [Seeds.mainState],
       this.programId
     )[0];
+    this.pricingState = web3.PublicKey.findProgramAddressSync(
+      [Seeds.pricingState],
+      this.programId
+    )[0];
+    this.paymentState = web3.PublicKey.findProgramAddressSync(
+      [Seeds.paymentState],
+      this.programId
+    )[0];
     this.baseSpl = new BaseSpl(this.connection)
   }
 
@@ -98,6 +128,29 @@ export class Connectivity {
     ], this.programId)[0]
   }
 
+  __getActivationTokenIssueAccount(activationToken: web3.PublicKey, index: BN | number): web3.PublicKey {
+    return web3.PublicKey.findProgramAddressSync([
+      Seeds.activationTokenIssue,
+      activationToken.toBuffer(),
+      new BN(index).toArrayLike(Buffer, "le", 8),
+    ], this.programId)[0]
+  }
+
+  __getActivationTokenReceiptAccount(issue: web3.PublicKey, holder: web3.PublicKey): web3.PublicKey {
+    return web3.PublicKey.findProgramAddressSync([
+      Seeds.activationTokenReceipt,
+      issue.toBuffer(),
+      holder.toBuffer(),
+    ], this.programId)[0]
+  }
+
+  __getGenerationStateAccount(generation: BN): web3.PublicKey {
+    return web3.PublicKey.findProgramAddressSync([
+      Seeds.generationState,
+      generation.toArrayLike(Buffer, "le", 8)
+    ], this.programId)[0]
+  }
+
   async initMainState(input: MainStateInput): Promise<Result<TxPassType<any>, any>> {
     try {
       this.reinit();
@@ -125,6 +178,7 @@ export class Connectivity {
         .accounts({
           owner: this.owner,
           mainState: this.mainState,
+          roleState: null,
         })
         .rpc();
       return { Ok: { signature } };
@@ -151,13 +205,405 @@ export class Connectivity {
     }
   }
 
-  async resetMain(): Promise<Result<TxPassType<any>, any>> {
+  async acceptMainStateOwnership(
+    newOwner: web3.Keypair
+  ): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const signature = await this.program.methods
+        .acceptMainStateOwnership()
+        .accounts({
+          newOwner: newOwner.publicKey,
+          mainState: this.mainState,
+        })
+        .signers([newOwner])
+        .rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  async cancelOwnershipTransfer(): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const signature = await this.program.methods
+        .cancelOwnershipTransfer()
+        .accounts({
+          owner: this.owner,
+          mainState: this.mainState,
+        })
+        .rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  async migrateMainState(): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const signature = await this.program.methods
+        .migrateMainState()
+        .accounts({
+          owner: this.owner,
+          mainState: this.mainState,
+          systemProgram,
+        })
+        .rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  /// `holderAta` backfills the profile's current holder
+  async migrateProfileState(profile: web3.PublicKey, holderAta: web3.PublicKey | null = null): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const signature = await this.program.methods
+        .migrateProfileState()
+        .accounts({
+          owner: this.owner,
+          mainState: this.mainState,
+          profile,
+          profileState: this.__getProfileStateAccount(profile),
+          holderAta,
+          systemProgram,
+        })
+        .rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  /// `withLegacyIssue` makes the invitations minted before issues existed redeemable
+  async migrateActivationTokenState(activationToken: web3.PublicKey, withLegacyIssue = false): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const signature = await this.program.methods
+        .migrateActivationTokenState()
+        .accounts({
+          owner: this.owner,
+          mainState: this.mainState,
+          activationToken,
+          activationTokenState: this.__getActivationTokenStateAccount(activationToken),
+          legacyIssue: withLegacyIssue ? this.__getActivationTokenIssueAccount(activationToken, 0) : null,
+          systemProgram,
+        })
+        .rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  /// receipt of the legacy issue of `activationToken` for the invitations `holder` has today
+  async importLegacyActivationTokens(activationToken: web3.PublicKey, holder: web3.PublicKey): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const legacyIssue = this.__getActivationTokenIssueAccount(activationToken, 0)
+      const signature = await this.program.methods
+        .importLegacyActivationTokens()
+        .accounts({
+          owner: this.owner,
+          mainState: this.mainState,
+          activationToken,
+          activationTokenState: this.__getActivationTokenStateAccount(activationToken),
+          legacyIssue,
+          holderAta: getAssociatedTokenAddressSync(activationToken, holder),
+          receipt: this.__getActivationTokenReceiptAccount(legacyIssue, holder),
+          systemProgram,
+        })
+        .rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  async migrateCollectionState(collection: web3.PublicKey): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const signature = await this.program.methods
+        .migrateCollectionState()
+        .accounts({
+          owner: this.owner,
+          mainState: this.mainState,
+          collection,
+          collectionState: this.__getCollectionStateAccount(collection),
+          systemProgram,
+        })
+        .rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  __getRoleStateAccount(role: Role, authority: web3.PublicKey): web3.PublicKey {
+    return web3.PublicKey.findProgramAddressSync([
+      Seeds.roleState,
+      utf8.encode(RoleSeeds[role]),
+      authority.toBuffer()
+    ], this.programId)[0]
+  }
+
+  async grantRole(role: Role, authority: web3.PublicKey): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const signature = await this.program.methods
+        .grantRole({ [role]: {} } as any)
+        .accounts({
+          owner: this.owner,
+          mainState: this.mainState,
+          authority,
+          roleState: this.__getRoleStateAccount(role, authority),
+          systemProgram,
+        })
+        .rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  async revokeRole(role: Role, authority: web3.PublicKey): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const signature = await this.program.methods
+        .revokeRole()
+        .accounts({
+          owner: this.owner,
+          mainState: this.mainState,
+          roleState: this.__getRoleStateAccount(role, authority),
+        })
+        .rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  // flags: bitset of PauseFlags
+  async pause(flags: number): Promise<Result<TxPassType<any>, any>> {
     try {
       this.reinit();
-      const signature = await this.program.methods.resetMain().accounts({
+      const signature = await this.program.methods
+        .pause(flags)
+        .accounts({
+          pauser: this.owner,
+          mainState: this.mainState,
+          roleState: null,
+        })
+        .rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  async unpause(flags: number): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const signature = await this.program.methods
+        .unpause(flags)
+        .accounts({
+          pauser: this.owner,
+          mainState: this.mainState,
+          roleState: null,
+        })
+        .rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  async setLineageConfig(input: { ancestors: number[], genesis: number }): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const signature = await this.program.methods
+        .setLineageConfig(input)
+        .accounts({
+          owner: this.owner,
+          mainState: this.mainState,
+          roleState: null,
+        })
+        .rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  /// `0` disables a limit, `window` is in seconds
+  async setInvitationQuota(input: { maxPerProfile: number, maxPerGeneration: number, window: number, maxPerWindow: number }): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const signature = await this.program.methods
+        .setInvitationQuota({
+          maxPerProfile: new BN(input.maxPerProfile),
+          maxPerGeneration: new BN(input.maxPerGeneration),
+          window: new BN(input.window),
+          maxPerWindow: new BN(input.maxPerWindow),
+        })
+        .accounts({
+          owner: this.owner,
+          mainState: this.mainState,
+          roleState: null,
+        })
+        .rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  /// `uriPrefixes` empty disables uri changes through `updateProfileMetadata`
+  async setMetadataConstraints(input: { maxNameLen: number, maxSymbolLen: number, uriPrefixes: string[] }): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const signature = await this.program.methods
+        .setMetadataConstraints(input)
+        .accounts({
+          owner: this.owner,
+          mainState: this.mainState,
+          roleState: null,
+        })
+        .rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  /// uris passed to `createCollection`, `initActivationToken` and `mintProfileByAt` get appended to these
+  async setBaseUris(input: { profile: string, activationToken: string, collection: string }): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const signature = await this.program.methods
+        .setBaseUris(input)
+        .accounts({
+          owner: this.owner,
+          mainState: this.mainState,
+          roleState: null,
+        })
+        .rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  async setNativeCollections(input: {
+    root: web3.PublicKey,
+    profile: web3.PublicKey,
+    badge: web3.PublicKey,
+    activationToken: web3.PublicKey,
+    pass: web3.PublicKey,
+  }): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      // metadata of every collection registered, unset entries are left as they are
+      const metadataAccounts = [input.root, input.profile, input.badge, input.activationToken, input.pass]
+        .filter((collection) => !collection.equals(web3.PublicKey.default))
+        .map((collection) => ({ pubkey: BaseMpl.getMetadataAccount(collection), isSigner: false, isWritable: false }))
+      const signature = await this.program.methods
+        .setNativeCollections(input)
+        .accounts({
+          owner: this.owner,
+          mainState: this.mainState,
+          roleState: null,
+        })
+        .remainingAccounts(metadataAccounts)
+        .rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  async initPricingState(input: PricingInput): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const signature = await this.program.methods
+        .initPricingState(input)
+        .accounts({
+          owner: this.owner,
+          mainState: this.mainState,
+          pricingState: this.pricingState,
+          systemProgram,
+        })
+        .rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  async updatePricingState(input: PricingInput): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const signature = await this.program.methods
+        .updatePricingState(input)
+        .accounts({
+          owner: this.owner,
+          mainState: this.mainState,
+          pricingState: this.pricingState,
+          roleState: null,
+        })
+        .rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  async initPaymentState(input: PaymentInput): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const signature = await this.program.methods
+        .initPaymentState(input)
+        .accounts({
+          owner: this.owner,
+          mainState: this.mainState,
+          paymentState: this.paymentState,
+          systemProgram,
+        })
+        .rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  /// existing mints keep their place, disable them instead of removing
+  async updatePaymentState(input: PaymentInput): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const signature = await this.program.methods
+        .updatePaymentState(input)
+        .accounts({
+          owner: this.owner,
+          mainState: this.mainState,
+          paymentState: this.paymentState,
+          roleState: null,
+        })
+        .rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  async scheduleReset(): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const signature = await this.program.methods.scheduleReset().accounts({
         owner: this.provider.publicKey,
         mainState: this.mainState,
-        systemProgram,
       }).rpc();
       return { Ok: { signature } };
     } catch (e) {
@@ -165,7 +611,68 @@ export class Connectivity {
     }
   }
 
-  async createCollection(input: { name?: string, symbol?: string, uri?: string, parrentCollection?: web3.PublicKey, collectionType: string }): Promise<Result<TxPassType<{ collection: string }>, any>> {
+  async cancelReset(): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const signature = await this.program.methods.cancelReset().accounts({
+        owner: this.provider.publicKey,
+        mainState: this.mainState,
+      }).rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  async releaseCollectionAuthority(collection: web3.PublicKey): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const signature = await this.program.methods
+        .releaseCollectionAuthority()
+        .accounts({
+          owner: this.provider.publicKey,
+          mainState: this.mainState,
+          collection,
+          collectionState: this.__getCollectionStateAccount(collection),
+          collectionMetadata: BaseMpl.getMetadataAccount(collection),
+          collectionEdition: BaseMpl.getEditionAccount(collection),
+          sysvarInstructions,
+          mplProgram,
+          systemProgram,
+        })
+        .rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  async executeReset(): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const mainStateInfo = await this.program.account.mainState.fetch(this.mainState)
+      const paymentStateInfo = await this.program.account.paymentState.fetchNullable(this.paymentState)
+      const paymentVaults = (paymentStateInfo?.mints ?? [])
+        .filter((payment) => !payment.mint.equals(NATIVE_MINT))
+        .map((payment) => ({
+          pubkey: getAssociatedTokenAddressSync(payment.mint, this.mainState, true),
+          isSigner: false,
+          isWritable: false,
+        }))
+      const signature = await this.program.methods.executeReset().accounts({
+        owner: this.provider.publicKey,
+        mainState: this.mainState,
+        oposVault: getAssociatedTokenAddressSync(mainStateInfo.oposToken, this.mainState, true),
+        paymentState: paymentStateInfo ? this.paymentState : null,
+        systemProgram,
+      }).remainingAccounts(paymentVaults).rpc();
+      return { Ok: { signature } };
+    } catch (e) {
+      return { Err: e };
+    }
+  }
+
+  async createCollection(input: { name?: string, symbol?: string, uri?: string, parrentCollection?: web3.PublicKey, collectionType: CollectionType }): Promise<Result<TxPassType<{ collection: string }>, any>> {
     try {
       this.reinit();
       let {
@@ -207,10 +714,11 @@ export class Connectivity {
       const cuBudgetIncIx = web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 3000_00 })
       this.txis.push(cuBudgetIncIx)
 
-      const ix = await this.program.methods.createCollection(name, symbol, uri, collectionType).accounts({
+      const ix = await this.program.methods.createCollection(name, symbol, uri, { [collectionType]: {} } as any).accounts({
         admin,
         adminAta,
         mainState: this.mainState,
+        roleState: null,
         associatedTokenProgram,
         collection: mint,
         collectionEdition: edition,
@@ -272,6 +780,7 @@ export class Connectivity {
       const ix = await this.program.methods.updateCollection(name, symbol, uri).accounts({
         admin,
         mainState: this.mainState,
+        roleState: null,
         associatedTokenProgram,
         collection: mint,
         collectionEdition: edition,
@@ -365,6 +874,7 @@ export class Connectivity {
         collection,
         mplProgram,
         profileState,
+        profileEarnings: web3.PublicKey.findProgramAddressSync([Seeds.vault, profile.toBuffer()], this.programId)[0],
         associatedTokenProgram,
         tokenProgram,
         systemProgram,
@@ -391,6 +901,63 @@ export class Connectivity {
     }
   }
 
+  /// mints a profile to `receiver` under `parentProfile` with the lineage it had before,
+  /// the receiver co-signs so the profile gets locked
+  async importProfile(input: ImportProfileInput, parentProfile: web3.PublicKey, receiver: web3.Keypair): Promise<Result<TxPassType<{ profile: string }>, any>> {
+    try {
+      this.reinit();
+      const admin = this.provider.publicKey;
+      if (!admin) throw "Wallet not found"
+      const mainStateInfo = await this.program.account.mainState.fetch(this.mainState)
+      const collection = mainStateInfo.profileCollection
+      const mintKp = web3.Keypair.generate()
+      const profile = mintKp.publicKey
+      const parentProfileStateInfo = await this.program.account.profileState.fetch(this.__getProfileStateAccount(parentProfile))
+      const childIndexPage = new BN(Math.floor(parentProfileStateInfo.lineage.totalChild.toNumber() / web3Consts.childIndexPageSize))
+      const childIndex = web3.PublicKey.findProgramAddressSync([
+        Seeds.childIndex,
+        parentProfile.toBuffer(),
+        childIndexPage.toArrayLike(Buffer, "le", 8),
+      ], this.programId)[0]
+
+      const cuBudgetIncIx = web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 3000_00 })
+      this.txis.push(cuBudgetIncIx)
+      const ix = await this.program.methods.importProfile(input).accounts({
+        admin,
+        receiver: receiver.publicKey,
+        mainState: this.mainState,
+        profile,
+        receiverProfileAta: getAssociatedTokenAddressSync(profile, receiver.publicKey),
+        profileState: this.__getProfileStateAccount(profile),
+        profileEarnings: web3.PublicKey.findProgramAddressSync([Seeds.vault, profile.toBuffer()], this.programId)[0],
+        profileMetadata: BaseMpl.getMetadataAccount(profile),
+        profileEdition: BaseMpl.getEditionAccount(profile),
+        parentProfile,
+        parentProfileState: this.__getProfileStateAccount(parentProfile),
+        childIndex,
+        collection,
+        collectionMetadata: BaseMpl.getMetadataAccount(collection),
+        collectionEdition: BaseMpl.getEditionAccount(collection),
+        sysvarInstructions,
+        mplProgram,
+        tokenProgram,
+        associatedTokenProgram,
+        systemProgram,
+      }).instruction();
+      this.txis.push(ix)
+
+      const tx = new web3.Transaction().add(...this.txis)
+      this.txis = []
+      const signature = await this.provider.sendAndConfirm(tx, [mintKp, receiver])
+      return {
+        Ok: { signature, info: { profile: profile.toBase58() } }
+      }
+    } catch (e) {
+      log({ error: e })
+      return { Err: e };
+    }
+  }
+
   return
 
   async initActivationToken(input: { name?: string, symbol?: string, uri?: string }): Promise<Result<TxPassType<{ activationToken: string }>, any>> {
@@ -464,7 +1031,7 @@ export class Connectivity {
   }
 
 
-  async mintActivationToken(amount: number, receiver?: web3.PublicKey | string,): Promise<Result<TxPassType<any>, any>> {
+  async mintActivationToken(amount: number, receiver?: web3.PublicKey | string, expiresAt?: number): Promise<Result<TxPassType<any>, any>> {
     try {
       this.reinit();
       const user = this.provider.publicKey;
@@ -493,36 +1060,10 @@ export class Connectivity {
       const parentProfileState = this.__getProfileStateAccount(parentProfile);
       const parentProfileStateInfo = await this.program.account.profileState.fetch(this.__getProfileStateAccount(parentProfile))
 
-      const {
-        //profiles
-        // genesisProfile,
-        // parentProfile,
-        grandParentProfile,
-        greatGrandParentProfile,
-        ggreateGrandParentProfile,
-        //
-        currentGreatGrandParentProfileHolder,
-        currentGgreatGrandParentProfileHolder,
-        currentGrandParentProfileHolder,
-        currentGenesisProfileHolder,
-        currentParentProfileHolder,
-        //
-        currentParentProfileHolderAta,
-        currentGenesisProfileHolderAta,
-        currentGrandParentProfileHolderAta,
-        currentGreatGrandParentProfileHolderAta,
-        currentGgreatGrandParentProfileHolderAta,
-        //
-        parentProfileHolderOposAta,
-        genesisProfileHolderOposAta,
-        grandParentProfileHolderOposAta,
-        greatGrandParentProfileHolderOposAta,
-        ggreatGrandParentProfileHolderOposAta,
-      } = await this.__getProfileHoldersInfo(parentProfileStateInfo.lineage, parentProfile, profile)
-
       const userOposAta = getAssociatedTokenAddressSync(oposToken, user)
-      
-      const ix = await this.program.methods.mintActivationToken(new BN(amount)).accounts({
+      const activationTokenIssue = this.__getActivationTokenIssueAccount(activationToken, activationTokenStateInfo.totalIssues)
+
+      const ix = await this.program.methods.mintActivationToken(new BN(amount), expiresAt ? new BN(expiresAt) : null, null).accounts({
         activationTokenState,
         tokenProgram,
         activationToken,
@@ -530,42 +1071,30 @@ export class Connectivity {
         profileState,
         minterProfileAta,
         mainState: this.mainState,
+        pricingState: this.pricingState,
         minter: user,
         receiverAta,
+        activationTokenIssue,
+        activationTokenReceipt: this.__getActivationTokenReceiptAccount(activationTokenIssue, receiver),
+        generationState: this.__getGenerationStateAccount(profileStateInfo.lineage.generation),
         //NOTE: Profile minting cost distributaion account
         oposToken,
         systemProgram,
         associatedTokenProgram,
         userOposAta,
         parentProfileState,
-
-        //Profiles
         parentProfile,
-        genesisProfile: profile,
-        grandParentProfile,
-        greatGrandParentProfile,
-        ggreateGrandParentProfile,
-
-        //verification ata
-        currentParentProfileHolderAta,
-        currentGrandParentProfileHolderAta,
-        currentGreatGrandParentProfileHolderAta,
-        currentGgreatGrandParentProfileHolderAta,
-        currentGenesisProfileHolderAta,
-        // profile owners
-        currentParentProfileHolder,
-        currentGrandParentProfileHolder,
-        currentGreatGrandParentProfileHolder,
-        currentGgreatGrandParentProfileHolder,
-        currentGenesisProfileHolder,
-
-        // holder opos ata
-        parentProfileHolderOposAta,
-        grandParentProfileHolderOposAta,
-        greatGrandParentProfileHolderOposAta,
-        ggreatGrandParentProfileHolderOposAta,
-        genesisProfileHolderOposAta,
-      }).instruction()
+
+        // paid in OPOS
+        paymentState: null,
+        paymentMint: null,
+        userPaymentAta: null,
+        tokenBonding: null,
+        curve: null,
+        bondingBaseStorage: null,
+      }).remainingAccounts(
+        await this.__getInvitationLineageAccounts(parentProfile, parentProfileStateInfo.ancestors)
+      ).instruction()
       this.txis.push(ix)
       const tx = new web3.Transaction().add(...this.txis)
       const signature = await this.provider.sendAndConfirm(tx)
@@ -612,6 +1141,7 @@ export class Connectivity {
       const signature = await this.program.methods.setCommonLut(lut).accounts({
         owner: this.provider.publicKey,
         mainState: this.mainState,
+        roleState: null,
       }).rpc();
 
       return {
@@ -628,85 +1158,28 @@ export class Connectivity {
     return res;
   }
 
-  async __getProfileHoldersInfo(input: LineageInfo, parentProfile: web3.PublicKey, genesisProfile: web3.PublicKey): Promise<{
-    //profiles:
-    parentProfile: web3.PublicKey,
-    grandParentProfile: web3.PublicKey,
-    greatGrandParentProfile: web3.PublicKey,
-    ggreateGrandParentProfile: web3.PublicKey,
-    genesisProfile: web3.PublicKey,
-    //Profile holder ata
-    currentParentProfileHolderAta: web3.PublicKey,
-    currentGrandParentProfileHolderAta: web3.PublicKey,
-    currentGreatGrandParentProfileHolderAta: web3.PublicKey,
-    currentGgreatGrandParentProfileHolderAta: web3.PublicKey,
-    currentGenesisProfileHolderAta: web3.PublicKey,
-    // profile owners
-    currentParentProfileHolder: web3.PublicKey,
-    currentGrandParentProfileHolder: web3.PublicKey,
-    currentGreatGrandParentProfileHolder: web3.PublicKey,
-    currentGgreatGrandParentProfileHolder: web3.PublicKey,
-    currentGenesisProfileHolder: web3.PublicKey,
-    // opos accounts
-    parentProfileHolderOposAta: web3.PublicKey,
-    grandParentProfileHolderOposAta: web3.PublicKey,
-    greatGrandParentProfileHolderOposAta: web3.PublicKey,
-    ggreatGrandParentProfileHolderOposAta: web3.PublicKey,
-    genesisProfileHolderOposAta: web3.PublicKey,
-  }> {
-    const grandParentProfile = input.parent
-    const greatGrandParentProfile = input.grandParent;
-    const ggreateGrandParentProfile = input.greatGrandParent;
-
-    const currentParentProfileHolderAta = (await this.connection.getTokenLargestAccounts(parentProfile)).value[0].address
-    const currentGrandParentProfileHolderAta = (await this.connection.getTokenLargestAccounts(grandParentProfile)).value[0].address
-    const currentGreatGrandParentProfileHolderAta = (await this.connection.getTokenLargestAccounts(greatGrandParentProfile)).value[0].address
-    const currentGgreatGrandParentProfileHolderAta = (await this.connection.getTokenLargestAccounts(ggreateGrandParentProfile)).value[0].address
-    const currentGenesisProfileHolderAta = (await this.connection.getTokenLargestAccounts(genesisProfile)).value[0].address
-
-    const atasInfo = await this.connection.getMultipleAccountsInfo([
-      currentParentProfileHolderAta,
-      currentGrandParentProfileHolderAta,
-      currentGreatGrandParentProfileHolderAta,
-      currentGgreatGrandParentProfileHolderAta,
-      currentGenesisProfileHolderAta
-    ])
-
-    const currentParentProfileHolder = unpackAccount(currentParentProfileHolderAta, atasInfo[0]).owner
-    const currentGrandParentProfileHolder = unpackAccount(currentGrandParentProfileHolderAta, atasInfo[1]).owner
-    const currentGreatGrandParentProfileHolder = unpackAccount(currentGreatGrandParentProfileHolderAta, atasInfo[2]).owner
-    const currentGgreatGrandParentProfileHolder = unpackAccount(currentGgreatGrandParentProfileHolderAta, atasInfo[3]).owner
-    const currentGenesisProfileHolder = unpackAccount(currentGenesisProfileHolderAta, atasInfo[4]).owner
-
-    return {
-      //profiles:
-      parentProfile,
-      grandParentProfile,
-      greatGrandParentProfile,
-      ggreateGrandParentProfile,
-      genesisProfile,
-      // profile holder profile ata
-      currentParentProfileHolderAta,
-      currentGrandParentProfileHolderAta,
-      currentGreatGrandParentProfileHolderAta,
-      currentGgreatGrandParentProfileHolderAta,
-      currentGenesisProfileHolderAta,
-
-      // profile holders
-      currentParentProfileHolder,
-      currentGrandParentProfileHolder,
-      currentGreatGrandParentProfileHolder,
-      currentGgreatGrandParentProfileHolder,
-      currentGenesisProfileHolder,
-
-      // profile holder oposAta
-      parentProfileHolderOposAta: getAssociatedTokenAddressSync(oposToken, currentParentProfileHolder),
-      grandParentProfileHolderOposAta: getAssociatedTokenAddressSync(oposToken, currentGrandParentProfileHolder),
-      greatGrandParentProfileHolderOposAta: getAssociatedTokenAddressSync(oposToken, currentGreatGrandParentProfileHolder),
-      ggreatGrandParentProfileHolderOposAta: getAssociatedTokenAddressSync(oposToken, currentGgreatGrandParentProfileHolder),
-      genesisProfileHolderOposAta: getAssociatedTokenAddressSync(oposToken, currentGenesisProfileHolder),
-    }
+  /// profile state, holder, holder OPOS ata and earnings account of each ancestor paid by
+  /// `mintActivationToken`, then of genesis
+  async __getInvitationLineageAccounts(parentProfile: web3.PublicKey, parentAncestors: web3.PublicKey[]): Promise<web3.AccountMeta[]> {
+    const mainStateInfo = await this.program.account.mainState.fetch(this.mainState)
+    const genesisProfile = mainStateInfo.genesisProfile
+    const lineage = [parentProfile, ...parentAncestors]
+      .slice(0, mainStateInfo.lineageDepth)
+      .map((ancestor) => ancestor.equals(web3.PublicKey.default) ? genesisProfile : ancestor)
+    lineage.push(genesisProfile)
+    const profileStates = lineage.map((profile) => this.__getProfileStateAccount(profile))
+    const holders = (await this.program.account.profileState.fetchMultiple(profileStates))
+      .map((state: any) => state?.currentHolder as web3.PublicKey | undefined)
+    // retired ancestors have no state anymore, their share goes to genesis
+    const genesisHolder = holders[holders.length - 1]
+    return lineage.flatMap((profile, i) => {
+      const holder = holders[i] ?? genesisHolder
+      return [
+        { pubkey: profileStates[i], isSigner: false, isWritable: false },
+        { pubkey: holder, isSigner: false, isWritable: true },
+        { pubkey: getAssociatedTokenAddressSync(oposToken, holder, true), isSigner: false, isWritable: true },
+        { pubkey: web3.PublicKey.findProgramAddressSync([Seeds.vault, profile.toBuffer()], this.programId)[0], isSigner: false, isWritable: true },
+      ]
+    })
   }
 }
-
-
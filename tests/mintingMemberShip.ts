@@ -66,7 +66,16 @@ describe("sop", () => {
         grandParent: 100 * 3,
         greatGrandParent: 100 * 2,
         genesis: 100 * 10,
-      }
+      },
+      minInvitationPrice: new BN(calcNonDecimalValue(1, 9)),
+      maxInvitationPrice: new BN(calcNonDecimalValue(100, 9)),
+      invitationPriceDistribution: {
+        parent: 100 * 50,
+        grandParent: 100 * 10,
+        greatGrandParent: 100 * 5,
+        ggreatGrandParent: 100 * 5,
+        genesis: 100 * 30,
+      },
     })
     log({ res })
     // if (res?.Err) throw "initialise mainstate failed"
@@ -74,57 +83,88 @@ describe("sop", () => {
 
   });
 
-  // let rootCollection: web3.PublicKey = null
-  // it("creating root Collections", async () => {
-
-  //   const name = "MMOSH Root Collection"
-  //   const symbol = "MMOSHDAO"
-  //   const uri = "https://shdw-drive.genesysgo.net/FuBjTTmQuqM7pGR2gFsaiBxDmdj8ExP5fzNwnZyE2PgC/root_collection.json"
-  //   const res = await adConn.createCollection({
-  //     name,
-  //     symbol,
-  //     uri,
-  //     collectionType: "root",
-  //     parrentCollection: rootCollection,
-  //   })
-  //   assert(res?.Ok, "Unable to create collection")
-  //   log({ sign: res.Ok.signature, collection: res.Ok.info.collection })
-  //   rootCollection = new web3.PublicKey(res.Ok.info.collection)
-
-  //   console.log("new root collection ",rootCollection.toBase58());
-  // })
-
-
-
-
-
-
-  // let badgeCollection: web3.PublicKey = null
-  // it("creating badge Collections", async () => {
-
-  //   const name = "MMOSH Badge Collection"
-  //   const symbol = "BADGES"
-  //   const uri = "https://shdw-drive.genesysgo.net/FuBjTTmQuqM7pGR2gFsaiBxDmdj8ExP5fzNwnZyE2PgC/badge_collection.json"
-  //   const res = await adConn.createCollection({
-  //     name,
-  //     symbol,
-  //     uri,
-  //     parrentCollection: web3Consts.rootCollection,
-  //     collectionType: "badge"
-  //   })
-  //   assert(res?.Ok, "Unable to create collection")
-  //   log({ sign: res.Ok.signature, collection: res.Ok.info.collection })
-  //   badgeCollection = new web3.PublicKey(res.Ok.info.collection)
-
-  //   console.log("new badge collection ",badgeCollection.toBase58());
-  // })
+  it("Initialise Pricing State!", async () => {
+    const accountInfo = await connection.getAccountInfo(adConn.pricingState)
+    if (accountInfo != null) return
+    const res = await adConn.initPricingState({
+      tiers: [
+        {
+          fromGeneration: new BN(0),
+          profileMintingCost: new BN(calcNonDecimalValue(20000, 9)),
+          invitationMintingCost: new BN(calcNonDecimalValue(1, 9)),
+        },
+        {
+          fromGeneration: new BN(5),
+          profileMintingCost: new BN(calcNonDecimalValue(40000, 9)),
+          invitationMintingCost: new BN(calcNonDecimalValue(2, 9)),
+        },
+      ]
+    })
+    log({ res })
+    assert(res?.Ok, "initialise pricing state failed")
+  });
 
+  it("Set base uris", async () => {
+    const res = await adConn.setBaseUris({
+      profile: "https://shdw-drive.genesysgo.net/FuBjTTmQuqM7pGR2gFsaiBxDmdj8ExP5fzNwnZyE2PgC/",
+      activationToken: "https://gateway.pinata.cloud/ipfs/",
+      collection: "https://shdw-drive.genesysgo.net/FuBjTTmQuqM7pGR2gFsaiBxDmdj8ExP5fzNwnZyE2PgC/",
+    })
+    assert(res?.Ok, "setting base uris failed")
+  });
 
+  let rootCollection: web3.PublicKey = null
+  it("creating root Collections", async () => {
+    const mainStateInfo = await adConn.program.account.mainState.fetch(adConn.mainState)
+    //skipping root collection minting if it already minted
+    if (mainStateInfo.rootCollection.toBase58() != web3.SystemProgram.programId.toBase58()) {
+      rootCollection = mainStateInfo.rootCollection;
+      console.log("existing root collection ", rootCollection.toBase58());
+      return;
+    }
 
+    const name = "MMOSH Root Collection"
+    const symbol = "MMOSHDAO"
+    const uri = "root_collection.json"
+    const res = await adConn.createCollection({
+      name,
+      symbol,
+      uri,
+      collectionType: "root",
+    })
+    assert(res?.Ok, "Unable to create collection")
+    log({ sign: res.Ok.signature, collection: res.Ok.info.collection })
+    rootCollection = new web3.PublicKey(res.Ok.info.collection)
 
+    console.log("new root collection ", rootCollection.toBase58());
+  })
 
+  let badgeCollection: web3.PublicKey = null
+  it("creating badge Collections", async () => {
+    const mainStateInfo = await adConn.program.account.mainState.fetch(adConn.mainState)
+    //skipping badge collection minting if it already minted
+    if (mainStateInfo.badgeCollection.toBase58() != web3.SystemProgram.programId.toBase58()) {
+      badgeCollection = mainStateInfo.badgeCollection;
+      console.log("existing badge collection ", badgeCollection.toBase58());
+      return;
+    }
 
+    const name = "MMOSH Badge Collection"
+    const symbol = "BADGES"
+    const uri = "badge_collection.json"
+    const res = await adConn.createCollection({
+      name,
+      symbol,
+      uri,
+      parrentCollection: rootCollection,
+      collectionType: "badge"
+    })
+    assert(res?.Ok, "Unable to create collection")
+    log({ sign: res.Ok.signature, collection: res.Ok.info.collection })
+    badgeCollection = new web3.PublicKey(res.Ok.info.collection)
 
+    console.log("new badge collection ", badgeCollection.toBase58());
+  })
 
   let profileCollection: web3.PublicKey = null
   it("creating profile Collections", async () => {
@@ -139,12 +179,12 @@ describe("sop", () => {
 
     const name = "MMOSH Profile Collection"
     const symbol = "PROFILES"
-    const uri = "https://shdw-drive.genesysgo.net/FuBjTTmQuqM7pGR2gFsaiBxDmdj8ExP5fzNwnZyE2PgC/profile_collection.json"
+    const uri = "profile_collection.json"
     const res = await adConn.createCollection({
       name,
       symbol,
       uri,
-      parrentCollection: web3Consts.rootCollection,
+      parrentCollection: rootCollection,
       collectionType: "profile"
     })
     assert(res?.Ok, "Unable to create collection")
@@ -153,6 +193,27 @@ describe("sop", () => {
     console.log("new profile collection ",profileCollection.toBase58());
   })
 
+  it("registering native collections", async () => {
+    const mainStateInfo = await adConn.program.account.mainState.fetch(adConn.mainState)
+    const res = await adConn.setNativeCollections({
+      root: rootCollection,
+      profile: profileCollection,
+      badge: badgeCollection,
+      activationToken: mainStateInfo.activationTokenCollection,
+      pass: mainStateInfo.passCollection,
+    })
+    assert(res?.Ok, "Unable to register native collections")
+
+    const res2 = await adConn.createCollection({
+      name: "MMOSH Profile Collection",
+      symbol: "PROFILES",
+      uri: "profile_collection.json",
+      parrentCollection: rootCollection,
+      collectionType: "profile"
+    })
+    assert(res2?.Err, "native profile collection got replaced")
+  })
+
 
 
   // it("update profile Collections", async () => {
@@ -226,7 +287,7 @@ describe("sop", () => {
       genesisProfile,
       oposToken,
       stateInfo.profileCollection,
-      web3Consts.badgeCollection,
+      badgeCollection,
       BaseMpl.getEditionAccount(stateInfo.profileCollection),
       BaseMpl.getMetadataAccount(stateInfo.profileCollection),
       web3.ComputeBudgetProgram.programId,
@@ -251,7 +312,7 @@ describe("sop", () => {
     const __collection = (await adConn.program.account.mainState.fetch(adConn.mainState)).profileCollection;
     const name = "OPOS Activation Badge"
     const symbol = "OPOSACT"
-    const uri = 'https://gateway.pinata.cloud/ipfs/QmTmLdPTzY5YRHF6AzVf4DSnVeBhXUhPDWaoAYZrF52jXX'
+    const uri = 'QmTmLdPTzY5YRHF6AzVf4DSnVeBhXUhPDWaoAYZrF52jXX'
     const res = await adConn.initActivationToken({ name: "Activation Token", symbol, uri })
     assert(res.Ok, "Failed to initialise activation Token")
     log("ActivationToken: ", res.Ok.info.activationToken)
@@ -297,7 +358,8 @@ describe("sop", () => {
   it("Initialise Subscription Token", async () => {
     const res = await userConn.initSubscriptionBadge({
       profile: userProfile,
-      name: "User Subscription"
+      name: "User Subscription",
+      uri: "QmTmLdPTzY5YRHF6AzVf4DSnVeBhXUhPDWaoAYZrF52jXX",
     })
     assert(res.Ok, "Failed to initalise activation token")
     log({ signature: res.Ok.signature, subscriptionToken: res.Ok.info.subscriptionToken })
@@ -310,6 +372,30 @@ describe("sop", () => {
     assert(res.Ok, "Failed to mint activation Token")
   })
 
+  it("Revoked invitations can't be redeemed against a live issue", async () => {
+    const leaker = web3.Keypair.generate()
+    await connection.confirmTransaction(await connection.requestAirdrop(leaker.publicKey, 2 * web3.LAMPORTS_PER_SOL))
+    const leakerConn = new UserConn(new anchor.AnchorProvider(connection, new anchor.Wallet(leaker), {}), programId)
+    const subscriptionTokenState = userConn.__getActivationTokenStateAccount(new web3.PublicKey(subscriptionToken))
+    const revokedIssue = (await program.account.activationTokenState.fetch(subscriptionTokenState)).totalIssues.toNumber()
+
+    const res = await userConn.mintSubscriptionToken({ subscriptionToken, receiver: leaker.publicKey })
+    assert(res.Ok, "Failed to mint activation Token")
+    const res2 = await userConn.mintSubscriptionToken({ subscriptionToken })
+    assert(res2.Ok, "Failed to mint activation Token")
+    const res3 = await userConn.revokeActivationTokens(subscriptionToken, [revokedIssue])
+    assert(res3.Ok, "Failed to revoke activation Tokens")
+
+    const res4 = await leakerConn.mintProfileByActivationToken({
+      activationToken: subscriptionToken,
+      genesisProfile,
+      name: "Leaked",
+      commonLut,
+      activationTokenIssue: userConn.__getActivationTokenIssueAccount(new web3.PublicKey(subscriptionToken), revokedIssue + 1),
+    })
+    assert(res4.Err, "Redeemed a revoked invitation against a live issue")
+  })
+
   //Subscription
   let subscriptionProfile: web3.PublicKey = null
   it("Mint profile by subscription profile", async () => {
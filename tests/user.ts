@@ -5,7 +5,6 @@ import { utf8 } from "@coral-xyz/anchor/dist/cjs/utils/bytes";
 import { TOKEN_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/utils/token";
 import { IDL, Sop } from "../target/types/sop";
 import {
-  LineageInfo,
   MainState,
   MainStateInput,
   Result,
@@ -17,7 +16,7 @@ import {
 import Config from "./web3Config.json";
 import { BaseMpl } from "./base/baseMpl";
 import { web3Consts } from './web3Consts'
-import { getAssociatedTokenAddress, getAssociatedTokenAddressSync, unpackAccount } from "@solana/spl-token";
+import { getAssociatedTokenAddress, getAssociatedTokenAddressSync, NATIVE_MINT, unpackAccount } from "@solana/spl-token";
 import { Metaplex, Metadata as MetadataM } from '@metaplex-foundation/js'
 import { BaseSpl } from "./base/baseSpl";
 
@@ -46,6 +45,8 @@ export class Connectivity {
   multiSignInfo: any[] = [];
   program: Program<Sop>;
   mainState: web3.PublicKey;
+  pricingState: web3.PublicKey;
+  paymentState: web3.PublicKey;
   connection: web3.Connection;
   metaplex: Metaplex
   baseSpl: BaseSpl
@@ -64,6 +65,14 @@ export class Connectivity {
       [Seeds.mainState],
       this.programId
     )[0];
+    this.pricingState = web3.PublicKey.findProgramAddressSync(
+      [Seeds.pricingState],
+      this.programId
+    )[0];
+    this.paymentState = web3.PublicKey.findProgramAddressSync(
+      [Seeds.paymentState],
+      this.programId
+    )[0];
     this.metaplex = new Metaplex(this.connection);
     this.baseSpl = new BaseSpl(this.connection)
   }
@@ -100,6 +109,53 @@ export class Connectivity {
       mint.toBuffer()
     ], this.programId)[0]
   }
+
+  __getActivationTokenIssueAccount(activationToken: web3.PublicKey, index: BN | number): web3.PublicKey {
+    return web3.PublicKey.findProgramAddressSync([
+      Seeds.activationTokenIssue,
+      activationToken.toBuffer(),
+      new BN(index).toArrayLike(Buffer, "le", 8),
+    ], this.programId)[0]
+  }
+
+  __getActivationTokenReceiptAccount(issue: web3.PublicKey, holder: web3.PublicKey): web3.PublicKey {
+    return web3.PublicKey.findProgramAddressSync([
+      Seeds.activationTokenReceipt,
+      issue.toBuffer(),
+      holder.toBuffer(),
+    ], this.programId)[0]
+  }
+
+  /// first issue of `activationToken` `holder` has invitations of left to redeem
+  async __getLiveActivationTokenIssue(activationToken: web3.PublicKey, totalIssues: BN, holder: web3.PublicKey): Promise<web3.PublicKey | null> {
+    const now = Math.floor(Date.now() / 1000)
+    for (let index = 0; index < totalIssues.toNumber(); index++) {
+      const issue = this.__getActivationTokenIssueAccount(activationToken, index)
+      const issueInfo = await this.program.account.activationTokenIssueState.fetchNullable(issue)
+      if (!issueInfo || issueInfo.revoked || issueInfo.redeemed.gte(issueInfo.amount)) continue
+      if (issueInfo.expiresAt.toNumber() != 0 && issueInfo.expiresAt.toNumber() <= now) continue
+      const receiptInfo = await this.program.account.activationTokenReceiptState.fetchNullable(this.__getActivationTokenReceiptAccount(issue, holder))
+      if (!receiptInfo || receiptInfo.amount.isZero()) continue
+      return issue
+    }
+    return null
+  }
+
+  __getGenerationStateAccount(generation: BN): web3.PublicKey {
+    return web3.PublicKey.findProgramAddressSync([
+      Seeds.generationState,
+      generation.toArrayLike(Buffer, "le", 8)
+    ], this.programId)[0]
+  }
+  /// index page the child number `child` of `parent` goes to
+  __getChildIndexAccount(parent: web3.PublicKey, child: number): web3.PublicKey {
+    const page = new BN(Math.floor(child / web3Consts.childIndexPageSize))
+    return web3.PublicKey.findProgramAddressSync([
+      Seeds.childIndex,
+      parent.toBuffer(),
+      page.toArrayLike(Buffer, "le", 8),
+    ], this.programId)[0]
+  }
   __getValutAccount(profile: web3.PublicKey): web3.PublicKey {
     return web3.PublicKey.findProgramAddressSync([
       Seeds.vault,
@@ -149,8 +205,12 @@ export class Connectivity {
         activationToken,
         genesisProfile,
         commonLut,
+        paymentMint,
+        maxPayment,
+        activationTokenIssue,
       } = input;
       if (typeof activationToken == 'string') activationToken = new web3.PublicKey(activationToken)
+      if (typeof paymentMint == 'string') paymentMint = new web3.PublicKey(paymentMint)
       if (typeof genesisProfile == 'string') genesisProfile = new web3.PublicKey(activationToken)
       if (typeof commonLut == 'string') commonLut = new web3.PublicKey(commonLut)
       symbol = symbol ?? ""
@@ -171,6 +231,8 @@ export class Connectivity {
       const profile = mintKp.publicKey
       const userProfileAta = getAssociatedTokenAddressSync(profile, user);
       const { ata: userActivationTokenAta } = await this.baseSpl.__getOrCreateTokenAccountInstruction({ mint: activationToken, owner: user }, this.ixCallBack)
+      activationTokenIssue = activationTokenIssue ?? await this.__getLiveActivationTokenIssue(activationToken, activationTokenStateInfo.totalIssues, user)
+      if (!activationTokenIssue) return { Err: "No unexpired invitations left" }
       const activationTokenMetadata = BaseMpl.getMetadataAccount(activationToken)
       const profileMetadata = BaseMpl.getMetadataAccount(profile)
       const profileEdition = BaseMpl.getEditionAccount(profile)
@@ -178,32 +240,6 @@ export class Connectivity {
       const parentProfileMetadata = BaseMpl.getMetadataAccount(parentProfile)
       const parentProfileState = this.__getProfileStateAccount(parentProfile)
       const subCollectionAuthorityRecord = BaseMpl.getCollectionAuthorityRecordAccount(profile, this.mainState)
-      const {
-        //profiles
-        // genesisProfile,
-        // parentProfile,
-        grandParentProfile,
-        greatGrandParentProfile,
-        ggreateGrandParentProfile,
-        //
-        currentGreatGrandParentProfileHolder,
-        currentGgreatGrandParentProfileHolder,
-        currentGrandParentProfileHolder,
-        currentGenesisProfileHolder,
-        currentParentProfileHolder,
-        //
-        currentParentProfileHolderAta,
-        currentGenesisProfileHolderAta,
-        currentGrandParentProfileHolderAta,
-        currentGreatGrandParentProfileHolderAta,
-        currentGgreatGrandParentProfileHolderAta,
-        //
-        parentProfileHolderOposAta,
-        genesisProfileHolderOposAta,
-        grandParentProfileHolderOposAta,
-        greatGrandParentProfileHolderOposAta,
-        ggreatGrandParentProfileHolderOposAta,
-      } = await this.__getProfileHoldersInfo(parentProfileStateInfo.lineage, parentProfile, genesisProfile)
       const userOposAta = getAssociatedTokenAddressSync(oposToken, user)
 
       const recentSlot = (await this.connection.getSlot() - 2);
@@ -242,7 +278,7 @@ export class Connectivity {
       this.txis.push(cuBudgetIncIx)
 
       const ix = await this.program.methods.mintProfileByAt(
-        name, symbol, uriHash
+        name, symbol, uriHash, maxPayment != undefined ? new BN(maxPayment) : null
       ).accounts({
         profile, 
         user,
@@ -250,6 +286,7 @@ export class Connectivity {
         userOposAta,
         userProfileAta,
         mainState: this.mainState, // 2
+        pricingState: this.pricingState,
         collection, // 4
         mplProgram, // 3
         profileState,
@@ -257,25 +294,27 @@ export class Connectivity {
         systemProgram, // 6
         profileEdition,
         activationToken,
+        activationTokenState,
         profileMetadata,
         collectionEdition, // 7
         collectionMetadata, // 8
         parentProfileState,
         sysvarInstructions, // 9
         userActivationTokenAta,
+        activationTokenIssue,
+        activationTokenReceipt: this.__getActivationTokenReceiptAccount(activationTokenIssue, user),
         associatedTokenProgram, // 10
         parentProfile,
-        currentParentProfileHolder,
-        currentGrandParentProfileHolder,
-        currentGreatGrandParentProfileHolder,
-        currentGgreatGrandParentProfileHolder,
-        currentGenesisProfileHolder,
-        parentProfileHolderOposAta,
-        grandParentProfileHolderOposAta,
-        greatGrandParentProfileHolderOposAta,
-        ggreatGrandParentProfileHolderOposAta,
-        genesisProfileHolderOposAta,
-      }).instruction()
+        profileEarnings: this.__getValutAccount(profile),
+        oposVault: getAssociatedTokenAddressSync(oposToken, this.mainState, true),
+        genesisEarnings: this.__getValutAccount(genesisProfile),
+        childIndex: this.__getChildIndexAccount(parentProfile, parentProfileStateInfo.lineage.totalChild.toNumber()),
+        ...(await this.__getPaymentAccounts(paymentMint, user)),
+        paymentVault: paymentMint && !paymentMint.equals(NATIVE_MINT) ? getAssociatedTokenAddressSync(paymentMint, this.mainState, true) : null,
+        ...(paymentMint ? { userOposAta: null, oposVault: null } : {}),
+      }).remainingAccounts(
+        await this.__getAncestorEarningsAccounts(parentProfile, parentProfileStateInfo.ancestors)
+      ).instruction()
       this.txis.push(ix)
 
 
@@ -386,9 +425,13 @@ export class Connectivity {
         subscriptionToken,
         receiver,
         parentProfile,
-        amount
+        amount,
+        paymentMint,
+        maxPayment,
+        expiresAt,
       } = input;
       amount = amount ?? 1;
+      if (typeof paymentMint == 'string') paymentMint = new web3.PublicKey(paymentMint)
 
       let subscriptionTokenState: web3.PublicKey = null;
       if (!subscriptionToken) {
@@ -420,79 +463,38 @@ export class Connectivity {
       const profileCollection = mainStateInfo.profileCollection;
       const profileCollectionState = await this.program.account.collectionState.fetch(this.__getCollectionStateAccount(profileCollection))
       const genesisProfile = profileCollectionState.genesisProfile;
-      const {
-        //profiles
-        // genesisProfile,
-        // parentProfile,
-        grandParentProfile,
-        greatGrandParentProfile,
-        ggreateGrandParentProfile,
-        //
-        currentGreatGrandParentProfileHolder,
-        currentGgreatGrandParentProfileHolder,
-        currentGrandParentProfileHolder,
-        currentGenesisProfileHolder,
-        currentParentProfileHolder,
-        //
-        currentParentProfileHolderAta,
-        currentGenesisProfileHolderAta,
-        currentGrandParentProfileHolderAta,
-        currentGreatGrandParentProfileHolderAta,
-        currentGgreatGrandParentProfileHolderAta,
-        //
-        parentProfileHolderOposAta,
-        genesisProfileHolderOposAta,
-        grandParentProfileHolderOposAta,
-        greatGrandParentProfileHolderOposAta,
-        ggreatGrandParentProfileHolderOposAta,
-      } = await this.__getProfileHoldersInfo(parentProfileStateInfo.lineage, parentProfile, genesisProfile)
 
       const userOposAta = getAssociatedTokenAddressSync(oposToken, user)
+      const activationTokenIssue = this.__getActivationTokenIssueAccount(subscriptionToken, activationTokenStateInfo.totalIssues)
 
-      const ix = await this.program.methods.mintActivationToken(new BN(amount)).accounts({
+      const ix = await this.program.methods.mintActivationToken(
+        new BN(amount), expiresAt ? new BN(expiresAt) : null, maxPayment != undefined ? new BN(maxPayment) : null
+      ).accounts({
         activationTokenState: subscriptionTokenState,
         tokenProgram,
         activationToken: subscriptionToken,
         profile: parentProfile,
         profileState,
+        pricingState: this.pricingState,
         minterProfileAta,
         mainState: this.mainState,
         minter: user,
         receiverAta,
+        activationTokenIssue,
+        activationTokenReceipt: this.__getActivationTokenReceiptAccount(activationTokenIssue, receiver),
+        generationState: this.__getGenerationStateAccount(parentProfileStateInfo.lineage.generation),
          //NOTE: Profile minting cost distributaion account
          oposToken,
          systemProgram,
          associatedTokenProgram,
          userOposAta,
          parentProfileState,
- 
-         //Profiles
-         parentProfile,
-         genesisProfile,
-         grandParentProfile,
-         greatGrandParentProfile,
-         ggreateGrandParentProfile,
- 
-         //verification ata
-         currentParentProfileHolderAta,
-         currentGrandParentProfileHolderAta,
-         currentGreatGrandParentProfileHolderAta,
-         currentGgreatGrandParentProfileHolderAta,
-         currentGenesisProfileHolderAta,
-         // profile owners
-         currentParentProfileHolder,
-         currentGrandParentProfileHolder,
-         currentGreatGrandParentProfileHolder,
-         currentGgreatGrandParentProfileHolder,
-         currentGenesisProfileHolder,
- 
-         // holder opos ata
-         parentProfileHolderOposAta,
-         grandParentProfileHolderOposAta,
-         greatGrandParentProfileHolderOposAta,
-         ggreatGrandParentProfileHolderOposAta,
-         genesisProfileHolderOposAta,
-      }).instruction()
+        parentProfile,
+        ...(await this.__getPaymentAccounts(paymentMint, user)),
+        ...(paymentMint ? { userOposAta: null } : {}),
+      }).remainingAccounts(
+        await this.__getInvitationLineageAccounts(parentProfile, parentProfileStateInfo.ancestors, paymentMint)
+      ).instruction()
       this.txis.push(ix)
 
       const tx = new web3.Transaction().add(...this.txis)
@@ -506,6 +508,373 @@ export class Connectivity {
   }
 
 
+  async initEarnings(profile: web3.PublicKey | string): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const user = this.provider.publicKey;
+      if (!user) throw "Wallet not found"
+      if (typeof profile == 'string') profile = new web3.PublicKey(profile)
+      const signature = await this.program.methods.initEarnings().accounts({
+        payer: user,
+        profile,
+        profileState: this.__getProfileStateAccount(profile),
+        earnings: this.__getValutAccount(profile),
+        systemProgram,
+      }).rpc()
+      return { Ok: { signature } }
+    } catch (error) {
+      log({ error })
+      return { Err: error }
+    }
+  }
+
+  async claimEarnings(profile: web3.PublicKey | string): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const user = this.provider.publicKey;
+      if (!user) throw "Wallet not found"
+      if (typeof profile == 'string') profile = new web3.PublicKey(profile)
+      const signature = await this.program.methods.claimEarnings().accounts({
+        holder: user,
+        profile,
+        profileState: this.__getProfileStateAccount(profile),
+        earnings: this.__getValutAccount(profile),
+        mainState: this.mainState,
+        oposToken,
+        oposVault: getAssociatedTokenAddressSync(oposToken, this.mainState, true),
+        holderOposAta: getAssociatedTokenAddressSync(oposToken, user),
+        tokenProgram,
+        associatedTokenProgram,
+        systemProgram,
+      }).rpc()
+      return { Ok: { signature } }
+    } catch (error) {
+      log({ error })
+      return { Err: error }
+    }
+  }
+
+  /// hands `amount` invitations of the issue `issueIndex` of `activationToken` to `receiver`
+  async transferActivationTokens(activationToken: web3.PublicKey | string, issueIndex: number, receiver: web3.PublicKey | string, amount = 1): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const user = this.provider.publicKey;
+      if (!user) throw "Wallet not found"
+      if (typeof activationToken == 'string') activationToken = new web3.PublicKey(activationToken)
+      if (typeof receiver == 'string') receiver = new web3.PublicKey(receiver)
+      const { ata: receiverAta } = await this.baseSpl.__getOrCreateTokenAccountInstruction({ mint: activationToken, owner: receiver, payer: user }, this.ixCallBack)
+      const activationTokenIssue = this.__getActivationTokenIssueAccount(activationToken, issueIndex)
+      const ix = await this.program.methods.transferActivationTokens(new BN(amount)).accounts({
+        holder: user,
+        mainState: this.mainState,
+        activationToken,
+        activationTokenIssue,
+        holderAta: getAssociatedTokenAddressSync(activationToken, user),
+        receiverAta,
+        holderReceipt: this.__getActivationTokenReceiptAccount(activationTokenIssue, user),
+        receiverReceipt: this.__getActivationTokenReceiptAccount(activationTokenIssue, receiver),
+        tokenProgram,
+        systemProgram,
+      }).instruction()
+      this.txis.push(ix)
+
+      const tx = new web3.Transaction().add(...this.txis)
+      this.txis = []
+      const signature = await this.provider.sendAndConfirm(tx)
+      return { Ok: { signature } }
+    } catch (error) {
+      log({ error })
+      return { Err: error }
+    }
+  }
+
+  /// revokes the issues `issueIndexes` of `activationToken`, as the holder of the profile
+  /// behind it or a community manager
+  async revokeActivationTokens(activationToken: web3.PublicKey | string, issueIndexes: number[]): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const user = this.provider.publicKey;
+      if (!user) throw "Wallet not found"
+      if (typeof activationToken == 'string') activationToken = new web3.PublicKey(activationToken)
+      const roleState = web3.PublicKey.findProgramAddressSync([
+        Seeds.roleState,
+        utf8.encode("community_manager"),
+        user.toBuffer(),
+      ], this.programId)[0]
+      const activationTokenState = this.__getActivationTokenStateAccount(activationToken)
+      const activationTokenStateInfo = await this.program.account.activationTokenState.fetch(activationTokenState)
+      const signature = await this.program.methods.revokeActivationTokens().accounts({
+        authority: user,
+        mainState: this.mainState,
+        activationToken,
+        activationTokenState,
+        profileState: this.__getProfileStateAccount(activationTokenStateInfo.parentProfile),
+        roleState: (await this.connection.getAccountInfo(roleState)) ? roleState : null,
+      }).remainingAccounts(
+        issueIndexes.map((index) => ({
+          pubkey: this.__getActivationTokenIssueAccount(activationToken as web3.PublicKey, index),
+          isSigner: false,
+          isWritable: true,
+        }))
+      ).rpc()
+      return { Ok: { signature } }
+    } catch (error) {
+      log({ error })
+      return { Err: error }
+    }
+  }
+
+  /// pays out minting cost credited in `paymentMint`, native SOL comes from the main state
+  async claimPaymentEarnings(profile: web3.PublicKey | string, paymentMint: web3.PublicKey | string): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const user = this.provider.publicKey;
+      if (!user) throw "Wallet not found"
+      if (typeof profile == 'string') profile = new web3.PublicKey(profile)
+      if (typeof paymentMint == 'string') paymentMint = new web3.PublicKey(paymentMint)
+      const paymentStateInfo = await this.program.account.paymentState.fetch(this.paymentState)
+      const index = paymentStateInfo.mints.findIndex((p) => p.mint.equals(paymentMint as web3.PublicKey))
+      if (index < 0) throw "Payment mint not found"
+      const isNative = paymentMint.equals(NATIVE_MINT)
+      const signature = await this.program.methods.claimPaymentEarnings(index).accounts({
+        holder: user,
+        profile,
+        profileState: this.__getProfileStateAccount(profile),
+        earnings: this.__getValutAccount(profile),
+        mainState: this.mainState,
+        paymentState: this.paymentState,
+        paymentMint,
+        paymentVault: isNative ? null : getAssociatedTokenAddressSync(paymentMint, this.mainState, true),
+        holderPaymentAta: isNative ? null : getAssociatedTokenAddressSync(paymentMint, user),
+        tokenProgram,
+        associatedTokenProgram,
+        systemProgram,
+      }).rpc()
+      return { Ok: { signature } }
+    } catch (error) {
+      log({ error })
+      return { Err: error }
+    }
+  }
+
+  /// `receiver` co-signs, their profile account gets frozen under the main state
+  async transferProfile(input: { profile: web3.PublicKey | string, receiver: web3.Keypair }): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const user = this.provider.publicKey;
+      if (!user) throw "Wallet not found"
+      let { profile, receiver } = input;
+      if (typeof profile == 'string') profile = new web3.PublicKey(profile)
+      const signature = await this.program.methods.transferProfile().accounts({
+        holder: user,
+        receiver: receiver.publicKey,
+        mainState: this.mainState,
+        profile,
+        profileState: this.__getProfileStateAccount(profile),
+        profileEdition: BaseMpl.getEditionAccount(profile),
+        holderProfileAta: getAssociatedTokenAddressSync(profile, user),
+        receiverProfileAta: getAssociatedTokenAddressSync(profile, receiver.publicKey),
+        mplProgram,
+        tokenProgram,
+        associatedTokenProgram,
+        systemProgram,
+      }).signers([receiver]).rpc()
+      return { Ok: { signature } }
+    } catch (error) {
+      log({ error })
+      return { Err: error }
+    }
+  }
+
+  /// points the profile's recorded holder at the wallet holding the nft, for profiles moved
+  /// outside `transferProfile`
+  async syncProfileHolder(profile: web3.PublicKey | string): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const holder = this.provider.publicKey;
+      if (!holder) throw "Wallet not found"
+      if (typeof profile == 'string') profile = new web3.PublicKey(profile)
+      const signature = await this.program.methods.syncProfileHolder().accounts({
+        holder,
+        profile,
+        profileState: this.__getProfileStateAccount(profile),
+        holderProfileAta: getAssociatedTokenAddressSync(profile, holder),
+      }).rpc()
+      return { Ok: { signature } }
+    } catch (error) {
+      log({ error })
+      return { Err: error }
+    }
+  }
+
+  /// `holder` defaults to the wallet, pass it when forcing the retirement as main state owner
+  async retireProfile(profile: web3.PublicKey | string, holder?: web3.PublicKey): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const user = this.provider.publicKey;
+      if (!user) throw "Wallet not found"
+      if (typeof profile == 'string') profile = new web3.PublicKey(profile)
+      if (!holder) holder = user
+      const signature = await this.program.methods.retireProfile().accounts({
+        authority: user,
+        holder,
+        mainState: this.mainState,
+        profile,
+        profileState: this.__getProfileStateAccount(profile),
+        earnings: this.__getValutAccount(profile),
+        profileEdition: BaseMpl.getEditionAccount(profile),
+        holderProfileAta: getAssociatedTokenAddressSync(profile, holder),
+        oposToken,
+        oposVault: getAssociatedTokenAddressSync(oposToken, this.mainState, true),
+        holderOposAta: getAssociatedTokenAddressSync(oposToken, holder),
+        mplProgram,
+        tokenProgram,
+        associatedTokenProgram,
+        systemProgram,
+      }).rpc()
+      return { Ok: { signature } }
+    } catch (error) {
+      log({ error })
+      return { Err: error }
+    }
+  }
+
+  /// drops the retired profile from the lineage of `profile`
+  async reparentProfile(input: { profile: web3.PublicKey | string, retiredProfile: web3.PublicKey | string }): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      let { profile, retiredProfile } = input;
+      if (typeof profile == 'string') profile = new web3.PublicKey(profile)
+      if (typeof retiredProfile == 'string') retiredProfile = new web3.PublicKey(retiredProfile)
+      const signature = await this.program.methods.reparentProfile().accounts({
+        mainState: this.mainState,
+        profile,
+        profileState: this.__getProfileStateAccount(profile),
+        retiredProfile,
+        retiredEarnings: this.__getValutAccount(retiredProfile),
+      }).rpc()
+      return { Ok: { signature } }
+    } catch (error) {
+      log({ error })
+      return { Err: error }
+    }
+  }
+
+  /// creates the lookup table of `profile` on-chain and stores it in its profile state
+  async initProfileLut(profile: web3.PublicKey | string): Promise<Result<TxPassType<{ lookupTable: string }>, any>> {
+    try {
+      this.reinit();
+      const user = this.provider.publicKey;
+      if (!user) throw "Wallet not found"
+      if (typeof profile == 'string') profile = new web3.PublicKey(profile)
+      const recentSlot = await this.connection.getSlot("finalized")
+      const [, lut] = web3.AddressLookupTableProgram.createLookupTable({
+        authority: this.mainState,
+        payer: user,
+        recentSlot,
+      })
+      const profileStateInfo = await this.program.account.profileState.fetch(this.__getProfileStateAccount(profile))
+      const signature = await this.program.methods.initProfileLut(new BN(recentSlot)).accounts({
+        payer: user,
+        mainState: this.mainState,
+        profile,
+        profileState: this.__getProfileStateAccount(profile),
+        lut,
+        addressLookupTableProgram,
+        systemProgram,
+      }).remainingAccounts(
+        await this.__getLineageHolderAtas(profile, profileStateInfo.ancestors)
+      ).rpc()
+      return { Ok: { signature, info: { lookupTable: lut.toBase58() } } }
+    } catch (error) {
+      log({ error })
+      return { Err: error }
+    }
+  }
+
+  /// adds the accounts missing from the lookup table of `profile`, e.g. after a lineage profile changed hands
+  async extendProfileLut(profile: web3.PublicKey | string): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const user = this.provider.publicKey;
+      if (!user) throw "Wallet not found"
+      if (typeof profile == 'string') profile = new web3.PublicKey(profile)
+      const profileStateInfo = await this.program.account.profileState.fetch(this.__getProfileStateAccount(profile))
+      const signature = await this.program.methods.extendProfileLut().accounts({
+        payer: user,
+        mainState: this.mainState,
+        profile,
+        profileState: this.__getProfileStateAccount(profile),
+        lut: profileStateInfo.lut,
+        addressLookupTableProgram,
+        systemProgram,
+      }).remainingAccounts(
+        await this.__getLineageHolderAtas(profile, profileStateInfo.ancestors)
+      ).rpc()
+      return { Ok: { signature } }
+    } catch (error) {
+      log({ error })
+      return { Err: error }
+    }
+  }
+
+  /// fields left out keep their current value
+  async updateProfileMetadata(profile: web3.PublicKey | string, input: { name?: string, symbol?: string, uri?: string }): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const user = this.provider.publicKey;
+      if (!user) throw "Wallet not found"
+      if (typeof profile == 'string') profile = new web3.PublicKey(profile)
+      const signature = await this.program.methods.updateProfileMetadata({
+        name: input.name ?? null,
+        symbol: input.symbol ?? null,
+        uri: input.uri ?? null,
+      }).accounts({
+        holder: user,
+        holderProfileAta: getAssociatedTokenAddressSync(profile, user),
+        mainState: this.mainState,
+        profile,
+        profileMetadata: BaseMpl.getMetadataAccount(profile),
+        profileEdition: BaseMpl.getEditionAccount(profile),
+        sysvarInstructions,
+        mplProgram,
+        systemProgram,
+      }).rpc()
+      return { Ok: { signature } }
+    } catch (error) {
+      log({ error })
+      return { Err: error }
+    }
+  }
+
+  async setInvitationPrice(input: { profile: web3.PublicKey | string, price: number }): Promise<Result<TxPassType<any>, any>> {
+    try {
+      this.reinit();
+      const user = this.provider.publicKey;
+      if (!user) throw "Wallet not found"
+      let { profile, price } = input;
+      if (typeof profile == 'string') profile = new web3.PublicKey(profile)
+      const profileState = this.__getProfileStateAccount(profile)
+      const profileStateInfo = await this.program.account.profileState.fetch(profileState)
+      const activationToken = profileStateInfo.activationToken;
+      if (!activationToken) throw "Subscription Token not initialised"
+
+      const signature = await this.program.methods.setInvitationPrice(new BN(price)).accounts({
+        holder: user,
+        holderProfileAta: getAssociatedTokenAddressSync(profile, user),
+        mainState: this.mainState,
+        profile,
+        profileState,
+        activationToken,
+        activationTokenState: this.__getActivationTokenStateAccount(activationToken),
+      }).rpc()
+      return { Ok: { signature } }
+    } catch (error) {
+      log({ error })
+      return { Err: error }
+    }
+  }
+
   async getUserInfo() {
     const user = this.provider.publicKey
     if (!user) throw "Wallet not found"
@@ -544,85 +913,79 @@ export class Connectivity {
     }
   }
 
-  async __getProfileHoldersInfo(input: LineageInfo, parentProfile: web3.PublicKey, genesisProfile: web3.PublicKey): Promise<{
-    //profiles:
-    parentProfile: web3.PublicKey,
-    grandParentProfile: web3.PublicKey,
-    greatGrandParentProfile: web3.PublicKey,
-    ggreateGrandParentProfile: web3.PublicKey,
-    genesisProfile: web3.PublicKey,
-    //Profile holder ata
-    currentParentProfileHolderAta: web3.PublicKey,
-    currentGrandParentProfileHolderAta: web3.PublicKey,
-    currentGreatGrandParentProfileHolderAta: web3.PublicKey,
-    currentGgreatGrandParentProfileHolderAta: web3.PublicKey,
-    currentGenesisProfileHolderAta: web3.PublicKey,
-    // profile owners
-    currentParentProfileHolder: web3.PublicKey,
-    currentGrandParentProfileHolder: web3.PublicKey,
-    currentGreatGrandParentProfileHolder: web3.PublicKey,
-    currentGgreatGrandParentProfileHolder: web3.PublicKey,
-    currentGenesisProfileHolder: web3.PublicKey,
-    // opos accounts
-    parentProfileHolderOposAta: web3.PublicKey,
-    grandParentProfileHolderOposAta: web3.PublicKey,
-    greatGrandParentProfileHolderOposAta: web3.PublicKey,
-    ggreatGrandParentProfileHolderOposAta: web3.PublicKey,
-    genesisProfileHolderOposAta: web3.PublicKey,
-  }> {
-    const grandParentProfile = input.parent
-    const greatGrandParentProfile = input.grandParent;
-    const ggreateGrandParentProfile = input.greatGrandParent;
-
-    const currentParentProfileHolderAta = (await this.connection.getTokenLargestAccounts(parentProfile)).value[0].address
-    const currentGrandParentProfileHolderAta = (await this.connection.getTokenLargestAccounts(grandParentProfile)).value[0].address
-    const currentGreatGrandParentProfileHolderAta = (await this.connection.getTokenLargestAccounts(greatGrandParentProfile)).value[0].address
-    const currentGgreatGrandParentProfileHolderAta = (await this.connection.getTokenLargestAccounts(ggreateGrandParentProfile)).value[0].address
-    const currentGenesisProfileHolderAta = (await this.connection.getTokenLargestAccounts(genesisProfile)).value[0].address
-
-    const atasInfo = await this.connection.getMultipleAccountsInfo([
-      currentParentProfileHolderAta,
-      currentGrandParentProfileHolderAta,
-      currentGreatGrandParentProfileHolderAta,
-      currentGgreatGrandParentProfileHolderAta,
-      currentGenesisProfileHolderAta
-    ])
-
-    const currentParentProfileHolder = unpackAccount(currentParentProfileHolderAta, atasInfo[0]).owner
-    const currentGrandParentProfileHolder = unpackAccount(currentGrandParentProfileHolderAta, atasInfo[1]).owner
-    const currentGreatGrandParentProfileHolder = unpackAccount(currentGreatGrandParentProfileHolderAta, atasInfo[2]).owner
-    const currentGgreatGrandParentProfileHolder = unpackAccount(currentGgreatGrandParentProfileHolderAta, atasInfo[3]).owner
-    const currentGenesisProfileHolder = unpackAccount(currentGenesisProfileHolderAta, atasInfo[4]).owner
+  /// profile token account of the current holder of `profile` and of each of its ancestors
+  async __getLineageHolderAtas(profile: web3.PublicKey, ancestors: web3.PublicKey[]): Promise<web3.AccountMeta[]> {
+    const profiles = [profile, ...ancestors]
+      .filter((p) => !p.equals(web3.PublicKey.default))
+      .filter((p, i, all) => all.findIndex((q) => q.equals(p)) == i)
+    const atas = []
+    for (const p of profiles) {
+      const holderAta = (await this.connection.getTokenLargestAccounts(p)).value[0]?.address
+      if (holderAta) atas.push({ pubkey: holderAta, isSigner: false, isWritable: false })
+    }
+    return atas
+  }
 
+  /// optional accounts for paying in `paymentMint` instead of OPOS, all null when paying in OPOS
+  async __getPaymentAccounts(paymentMint: web3.PublicKey | undefined, payer: web3.PublicKey) {
+    if (!paymentMint) {
+      return { paymentState: null, paymentMint: null, userPaymentAta: null, tokenBonding: null, curve: null, bondingBaseStorage: null }
+    }
+    const paymentStateInfo = await this.program.account.paymentState.fetch(this.paymentState)
+    const payment = paymentStateInfo.mints.find((p) => p.mint.equals(paymentMint))
+    if (!payment || payment.disabled) throw "Payment mint not accepted"
+    let tokenBonding = null, curve = null, bondingBaseStorage = null
+    if (!payment.tokenBonding.equals(web3.PublicKey.default)) {
+      const tokenBondingInfo = await this.program.account.tokenBondingV0.fetch(payment.tokenBonding)
+      tokenBonding = payment.tokenBonding
+      curve = tokenBondingInfo.curve
+      bondingBaseStorage = tokenBondingInfo.baseStorage
+    }
     return {
-      //profiles:
-      parentProfile,
-      grandParentProfile,
-      greatGrandParentProfile,
-      ggreateGrandParentProfile,
-      genesisProfile,
-      // profile holder profile ata
-      currentParentProfileHolderAta,
-      currentGrandParentProfileHolderAta,
-      currentGreatGrandParentProfileHolderAta,
-      currentGgreatGrandParentProfileHolderAta,
-      currentGenesisProfileHolderAta,
-
-      // profile holders
-      currentParentProfileHolder,
-      currentGrandParentProfileHolder,
-      currentGreatGrandParentProfileHolder,
-      currentGgreatGrandParentProfileHolder,
-      currentGenesisProfileHolder,
-
-      // profile holder oposAta
-      parentProfileHolderOposAta: getAssociatedTokenAddressSync(oposToken, currentParentProfileHolder),
-      grandParentProfileHolderOposAta: getAssociatedTokenAddressSync(oposToken, currentGrandParentProfileHolder),
-      greatGrandParentProfileHolderOposAta: getAssociatedTokenAddressSync(oposToken, currentGreatGrandParentProfileHolder),
-      ggreatGrandParentProfileHolderOposAta: getAssociatedTokenAddressSync(oposToken, currentGgreatGrandParentProfileHolder),
-      genesisProfileHolderOposAta: getAssociatedTokenAddressSync(oposToken, currentGenesisProfileHolder),
+      paymentState: this.paymentState,
+      paymentMint,
+      userPaymentAta: paymentMint.equals(NATIVE_MINT) ? null : getAssociatedTokenAddressSync(paymentMint, payer),
+      tokenBonding,
+      curve,
+      bondingBaseStorage,
     }
   }
 
+  /// profile state, holder, holder ata and earnings account of each ancestor paid by
+  /// `mintActivationToken`, then of genesis. Native SOL is paid to the holder's wallet
+  async __getInvitationLineageAccounts(parentProfile: web3.PublicKey, parentAncestors: web3.PublicKey[], paymentMint?: web3.PublicKey): Promise<web3.AccountMeta[]> {
+    const mainStateInfo = await this.program.account.mainState.fetch(this.mainState)
+    const genesisProfile = mainStateInfo.genesisProfile
+    const lineage = [parentProfile, ...parentAncestors]
+      .slice(0, mainStateInfo.lineageDepth)
+      .map((ancestor) => ancestor.equals(web3.PublicKey.default) ? genesisProfile : ancestor)
+    lineage.push(genesisProfile)
+    const profileStates = lineage.map((profile) => this.__getProfileStateAccount(profile))
+    const holders = (await this.program.account.profileState.fetchMultiple(profileStates))
+      .map((state: any) => state?.currentHolder as web3.PublicKey | undefined)
+    // retired ancestors have no state anymore, their share goes to genesis
+    const genesisHolder = holders[holders.length - 1]
+    const mint = paymentMint ?? oposToken
+    return lineage.flatMap((profile, i) => {
+      const holder = holders[i] ?? genesisHolder
+      const holderAta = mint.equals(NATIVE_MINT) ? holder : getAssociatedTokenAddressSync(mint, holder, true)
+      return [
+        { pubkey: profileStates[i], isSigner: false, isWritable: false },
+        { pubkey: holder, isSigner: false, isWritable: true },
+        { pubkey: holderAta, isSigner: false, isWritable: true },
+        { pubkey: this.__getValutAccount(profile), isSigner: false, isWritable: true },
+      ]
+    })
+  }
+
+  /// earnings account of each ancestor credited by `mintProfileByAt`
+  async __getAncestorEarningsAccounts(parentProfile: web3.PublicKey, parentAncestors: web3.PublicKey[]): Promise<web3.AccountMeta[]> {
+    const mainStateInfo = await this.program.account.mainState.fetch(this.mainState)
+    return [parentProfile, ...parentAncestors]
+      .slice(0, mainStateInfo.lineageDepth)
+      .map((ancestor) => ancestor.equals(web3.PublicKey.default) ? mainStateInfo.genesisProfile : ancestor)
+      .map((ancestor) => ({ pubkey: this.__getValutAccount(ancestor), isSigner: false, isWritable: true }))
+  }
+
 }
 
@@ -19,5 +19,13 @@ export const web3Consts = {
     collectionState: utf8.encode("collection_state1"),
     activationTokenState: utf8.encode("activation_token_state1"),
     vault: utf8.encode("vault1"),
+    roleState: utf8.encode("role_state1"),
+    pricingState: utf8.encode("pricing_state1"),
+    paymentState: utf8.encode("payment_state1"),
+    activationTokenIssue: utf8.encode("activation_token_issue1"),
+    activationTokenReceipt: utf8.encode("activation_token_receipt1"),
+    generationState: utf8.encode("generation_state1"),
+    childIndex: utf8.encode("child_index1"),
   },
+  childIndexPageSize: 32,
 }
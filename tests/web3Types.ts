@@ -14,6 +14,12 @@ const mainStateInputTypeName = "MainStateInput";
 const mintProfileByAdminInput = "MintProfileByAdminInput"
 export type MainStateInput = IdlTypes<Sop>[typeof mainStateInputTypeName];
 export type MintProfileByAdminInput = IdlTypes<Sop>[typeof mintProfileByAdminInput];
+const importProfileInput = "ImportProfileInput"
+export type ImportProfileInput = IdlTypes<Sop>[typeof importProfileInput];
+const pricingInputTypeName = "PricingInput";
+export type PricingInput = IdlTypes<Sop>[typeof pricingInputTypeName];
+const paymentInputTypeName = "PaymentInput";
+export type PaymentInput = IdlTypes<Sop>[typeof paymentInputTypeName];
 
 
 //EXTRA (Out of IDL)
@@ -38,6 +44,12 @@ export type _MintProfileByAtInput = {
   activationToken: string | web3.PublicKey
   genesisProfile: string | web3.PublicKey
   commonLut: string | web3.PublicKey
+  /// accepted payment mint to pay with instead of OPOS
+  paymentMint?: string | web3.PublicKey
+  /// most of `paymentMint` the user pays, required for curve priced mints
+  maxPayment?: number
+  /// issue to redeem against, the first live one the user holds invitations of when left out
+  activationTokenIssue?: web3.PublicKey
 }
 
 export type _MintSubscriptionToken = {
@@ -45,5 +57,11 @@ export type _MintSubscriptionToken = {
   subscriptionToken?: web3.PublicKey | string,
   receiver?: web3.PublicKey | string,
   amount?: number
+  /// accepted payment mint to pay with instead of OPOS
+  paymentMint?: web3.PublicKey | string,
+  /// most of `paymentMint` the user pays, required for curve priced mints
+  maxPayment?: number,
+  /// unix time the invitations expire at, never when left out
+  expiresAt?: number,
 }
 